@@ -0,0 +1,35 @@
+use std::env::args;
+use std::fs::read_to_string;
+
+use land_nft_contract::merkle::{leaf_hash, parse_csv, proof, root};
+
+/// Prints the merkle root of an allowlist csv and the proof of each of its addresses,
+/// cargo run --example merkle -- examples/allowlist.csv
+fn main() {
+
+    let path = args().nth(1).expect("Usage: merkle <allowlist.csv>");
+
+    let csv = read_to_string(&path).expect("Failed to read the allowlist");
+
+    let entries = parse_csv(csv.as_str()).expect("Failed to parse the allowlist");
+
+    let leaves = entries.iter().map(|(a, n)| leaf_hash(a, *n)).collect::<Vec<_>>();
+
+    let root = root(&leaves).expect("The allowlist is empty");
+
+    println!("{{");
+    println!("  \"root\": \"{}\",", hex::encode(root));
+    println!("  \"proofs\": {{");
+
+    for (i, (address, allocation)) in entries.iter().enumerate() {
+
+        let proof = proof(&leaves, i).iter().map(|h| format!("\"{}\"", hex::encode(h))).collect::<Vec<_>>();
+
+        let sep = if i + 1 < entries.len() { "," } else { "" };
+
+        println!("    \"{}\": {{ \"allocation\": {}, \"proof\": [{}] }}{}", address, allocation, proof.join(", "), sep);
+    }
+
+    println!("  }}");
+    println!("}}");
+}
@@ -3,11 +3,11 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use land_nft_contract::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use land_nft_contract::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, Cw20HookMsg};
 use land_nft_contract::state::{LandNft, LandNftMediaType, LandNftRoyalty,Attribute};
 use land_nft_contract::resp::*;
 use cw721::{AllNftInfoResponse, NftInfoResponse, NumTokensResponse, TokensResponse};
-use cw721_metadata_onchain::Metadata;
+use land_nft_contract::ins::Metadata;
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -18,6 +18,7 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
     export_schema(&schema_for!(LandNft), &out_dir);
     export_schema(&schema_for!(LandNftMediaType), &out_dir);
     export_schema(&schema_for!(LandNftRoyalty), &out_dir);
@@ -28,6 +29,25 @@ fn main() {
     export_schema(&schema_for!(LandNftMediaTypesResponse), &out_dir);
     export_schema(&schema_for!(OptionalLandNftResponse), &out_dir);
     export_schema(&schema_for!(LandNftCountResponse), &out_dir);
+    export_schema(&schema_for!(AdminsResponse), &out_dir);
+    export_schema(&schema_for!(RolesResponse), &out_dir);
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(TreasuriesResponse), &out_dir);
+    export_schema(&schema_for!(PendingBalanceResponse), &out_dir);
+    export_schema(&schema_for!(RemainingPlotsResponse), &out_dir);
+    export_schema(&schema_for!(CatalogStatsResponse), &out_dir);
+    export_schema(&schema_for!(RoyaltyInfoResponse), &out_dir);
+    export_schema(&schema_for!(RoyaltiesInfoResponse), &out_dir);
+    export_schema(&schema_for!(CheckRoyaltiesResponse), &out_dir);
+    export_schema(&schema_for!(ListingResponse), &out_dir);
+    export_schema(&schema_for!(ListingsResponse), &out_dir);
+    export_schema(&schema_for!(AuctionResponse), &out_dir);
+    export_schema(&schema_for!(ReservationResponse), &out_dir);
+    export_schema(&schema_for!(Cw20PricesResponse), &out_dir);
+    export_schema(&schema_for!(PaymentPlanResponse), &out_dir);
+    export_schema(&schema_for!(InstallmentPlanResponse), &out_dir);
+    export_schema(&schema_for!(CurrentPriceResponse), &out_dir);
+    export_schema(&schema_for!(MintEligibilityResponse), &out_dir);
     export_schema(&schema_for!(AllNftInfoResponse<Metadata>), &out_dir);
     export_schema(&schema_for!(TokensResponse), &out_dir);
     export_schema(&schema_for!(NumTokensResponse), &out_dir);
@@ -4,15 +4,20 @@ use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response,
 use cw2::set_contract_version;
 
 use crate::error::ContractError;
-use crate::msg::{ ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{LandNftMediaType, LandNftRoyalty};
+use crate::msg::{ ExecuteMsg, InstantiateMsg, QueryMsg, Cw2981QueryMsg};
+use crate::state::{LandNftMediaType, LandNftRoyalty, ADMINS, CONFIG, Config, DEFAULT_LAND_NFT_SYMBOL, 
+    ROLES, ROLE_MINTER,
+    DEFAULT_PRICE_DENOM, DEFAULT_EXTERN_URL_PREFIX, DEFAULT_MAX_ROYALTY_BPS, DutchAuction, cw20_contract};
 use crate::ins::{add_land_nft, add_land_nft_media_type, add_land_nft_royalty, 
     remove_land_nft_royalty, remove_land_nft_media_type, update_land_nft, 
-    remove_land_nft, mint_land_nft, ins_land_nft_for_minting, ins_and_mint_nft};
+    remove_land_nft, mint_land_nft, ins_land_nft_for_minting, init_collection,
+    add_admin, remove_admin, grant_role, revoke_role, update_land_nft_description,
+    update_config, validate_treasuries, set_treasuries, withdraw, batch_mint_land_nft, execute_cw721, burn_land_nft, list_for_sale, cancel_listing, buy, start_auction, place_bid, settle_auction, set_dutch_auction, clear_dutch_auction, set_sale_phases, update_allowlist, set_reservation_terms, reserve_land_nft, release_reservation, set_cw20_price, receive_cw20, set_payment_plan, start_payment_plan, pay_installment, repossess_land_nft, validate_address, validate_max_royalty};
 use crate::get::{get_all_land_nfts, get_land_nft_media_types, 
     get_land_nft_royalties, get_land_nft, get_all_minted_tokens,
     get_minted_tokens_by_owner, get_minted_tokens_count, get_nft_info, 
-    get_all_nft_info, get_all_land_nfts_by,land_nfts_count_by, get_by_index_in};
+    get_all_nft_info, get_all_land_nfts_by, get_all_land_nfts_by_owner, get_all_land_nfts_by_denom, land_nfts_count_by, get_catalog_stats, get_royalty_info, get_cw2981_royalty_info, check_royalties, get_listing, get_listings, get_auction, get_reservation, get_cw20_prices, get_payment_plan, get_installment_plan, get_current_price, get_mint_eligibility, get_by_index_in,
+    list_admins, roles_of, get_config, get_treasuries, get_pending_balance, get_remaining_plots};
 
 // version info for migration info
 pub const CONTRACT_NAME: &str = "crates.io:land_nft_contract";
@@ -26,11 +31,50 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let mut admins : Vec<Addr> = vec![];
+
+    for a in msg.admins.iter() {
+
+        let admin = validate_address(deps.api, a.as_str())?;
+        if !admins.contains(&admin) {
+            admins.push(admin);
+        }
+    }
+
+    // the instantiator becomes the sole admin when none is given
+    if admins.is_empty() {
+        admins.push(info.sender.clone());
+    }
+
+    ADMINS.save(deps.storage, &admins)?;
+
+    let minter = match msg.minter {
+        Some(m) => validate_address(deps.api, m.as_str())?,
+        None => info.sender.clone(),
+    };
+
+    ROLES.save(deps.storage, &minter, &vec![ROLE_MINTER])?;
+
+    let config = Config {
+        name : msg.name,
+        symbol : msg.symbol.unwrap_or_else(|| DEFAULT_LAND_NFT_SYMBOL.to_string()),
+        price_denom : msg.price_denom.unwrap_or_else(|| DEFAULT_PRICE_DENOM.to_string()),
+        external_url_prefix : msg.external_url_prefix.unwrap_or_else(|| DEFAULT_EXTERN_URL_PREFIX.to_string()),
+        minter,
+        treasuries : validate_treasuries(deps.api, msg.treasuries)?,
+        max_royalty_bps : validate_max_royalty(msg.max_royalty_bps.unwrap_or(DEFAULT_MAX_ROYALTY_BPS))?,
+        sale_phases : vec![],
+        reservation_terms : None,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    init_collection(deps.storage, &config)?;
    
     Ok(Response::new()
         .add_attribute("method", "instantiate")
-        .add_attribute("owner", info.sender)
-        .add_attribute("count", msg.count.to_string()))
+        .add_attribute("owner", info.sender))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -40,6 +84,13 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+
+    // cw20 denoms only come in through the Receive hook, never as native funds
+    if let Some(c) = info.funds.iter().find(|c| cw20_contract(c.denom.as_str()).is_some()) {
+
+        return Err(ContractError::Cw20NotAccepted { contract : c.denom.clone() });
+    }
+
     match msg {
        
         ExecuteMsg::AddLandNft {
@@ -52,6 +103,10 @@ pub fn execute(
         }=> update_land_nft(deps, _env, info, for_key, name, description, total_size, each_size, size_unit, 
             addr, total_lands, price, price_denom),
     
+        ExecuteMsg::UpdateLandNftDescription {
+            for_key, name, description
+        }=> update_land_nft_description(deps, _env, info, for_key, name, description),
+
         ExecuteMsg::RemoveLandNft {
             for_key
         }=> remove_land_nft(deps, _env, info, for_key),
@@ -63,8 +118,7 @@ pub fn execute(
             is_default
         }=> {
 
-            let media_type = LandNftMediaType{ url : url, media_type : 
-                media_type, is_default : is_default, date_updated : Some(_env.block.time) };
+            let media_type = LandNftMediaType{ url, media_type, is_default, date_updated : Some(_env.block.time) };
             add_land_nft_media_type(deps, _env, info,for_key,media_type)
 
         },
@@ -77,8 +131,8 @@ pub fn execute(
         } => {
 
             let royalty = LandNftRoyalty{ creator_wallet :
-                deps.api.addr_validate(creator_wallet.as_str()).expect("Failed to unwrap"), index : index, 
-                royalty : royalty, date_updated : Some(_env.block.time)};
+                validate_address(deps.api, creator_wallet.as_str())?, index, 
+                royalty, date_updated : Some(_env.block.time)};
             add_land_nft_royalty(deps, _env, info, for_key, royalty)
 
         },
@@ -91,23 +145,169 @@ pub fn execute(
         ExecuteMsg::RemoveLandNftRoyalty {
             for_key,
             creator_wallet,
-        } => remove_land_nft_royalty(deps, _env, info,for_key, Addr::unchecked(creator_wallet)),
+        } => remove_land_nft_royalty(deps, _env, info,for_key, creator_wallet),
 
-        ExecuteMsg::InstantiateMinting {
-            for_key
-        }=> ins_land_nft_for_minting(deps, _env, info, for_key),
+        ExecuteMsg::InstantiateMinting {}=> ins_land_nft_for_minting(deps, _env, info),
 
         ExecuteMsg::MintLandNft {
             for_key,
-            external_url_prefix
-        }=> mint_land_nft(deps, _env, info, for_key,external_url_prefix),
-    
-        ExecuteMsg::InsAndMintLandNft {
-            for_key,
-            external_url_prefix
-        }=>{
-            ins_and_mint_nft(deps, _env, info, for_key, external_url_prefix)
+            plot,
+            external_url_prefix,
+            proof,
+            allocation
+        }=> mint_land_nft(deps, _env, info, for_key, plot, external_url_prefix, proof, allocation),
+
+        ExecuteMsg::BatchMintLandNft {
+            keys,
+            external_url_prefix,
+            proof,
+            allocation
+        }=> batch_mint_land_nft(deps, _env, info, keys, external_url_prefix, proof, allocation),
+
+        ExecuteMsg::TransferNft {
+            recipient, token_id
+        }=> execute_cw721(deps, _env, info, 
+            cw721_base::msg::ExecuteMsg::TransferNft { recipient, token_id }),
+
+        ExecuteMsg::SendNft {
+            contract, token_id, msg
+        }=> execute_cw721(deps, _env, info, 
+            cw721_base::msg::ExecuteMsg::SendNft { contract, token_id, msg }),
+
+        ExecuteMsg::Approve {
+            spender, token_id, expires
+        }=> execute_cw721(deps, _env, info, 
+            cw721_base::msg::ExecuteMsg::Approve { spender, token_id, expires }),
+
+        ExecuteMsg::Revoke {
+            spender, token_id
+        }=> execute_cw721(deps, _env, info, 
+            cw721_base::msg::ExecuteMsg::Revoke { spender, token_id }),
+
+        ExecuteMsg::ApproveAll {
+            operator, expires
+        }=> execute_cw721(deps, _env, info, 
+            cw721_base::msg::ExecuteMsg::ApproveAll { operator, expires }),
+
+        ExecuteMsg::RevokeAll {
+            operator
+        }=> execute_cw721(deps, _env, info, 
+            cw721_base::msg::ExecuteMsg::RevokeAll { operator }),
+
+        ExecuteMsg::ListForSale {
+            token_id, price, denom, expires
+        }=> list_for_sale(deps, _env, info, token_id, price, denom, expires),
+
+        ExecuteMsg::CancelListing {
+            token_id
+        }=> cancel_listing(deps, _env, info, token_id),
+
+        ExecuteMsg::Buy {
+            token_id
+        }=> buy(deps, _env, info, token_id),
+
+        ExecuteMsg::StartAuction {
+            for_key, reserve_price, denom, start, end, min_increment
+        }=> start_auction(deps, _env, info, for_key, reserve_price, denom, start, end, min_increment),
+
+        ExecuteMsg::PlaceBid {
+            for_key
+        }=> place_bid(deps, _env, info, for_key),
+
+        ExecuteMsg::SettleAuction {
+            for_key
+        }=> settle_auction(deps, _env, info, for_key),
+
+        ExecuteMsg::SetDutchAuction {
+            for_key, mode, start_price, floor, start, decay_interval, decay_step
+        }=> {
+
+            let auction = DutchAuction { mode, start_price, floor, 
+                start, decay_interval, decay_step };
+            set_dutch_auction(deps, _env, info, for_key, auction)
         },
+
+        ExecuteMsg::ClearDutchAuction {
+            for_key
+        }=> clear_dutch_auction(deps, _env, info, for_key),
+
+        ExecuteMsg::SetSalePhases {
+            phases
+        }=> set_sale_phases(deps, _env, info, phases),
+
+        ExecuteMsg::AddToAllowlist {
+            phase, addresses
+        }=> update_allowlist(deps, _env, info, phase, addresses, true),
+
+        ExecuteMsg::RemoveFromAllowlist {
+            phase, addresses
+        }=> update_allowlist(deps, _env, info, phase, addresses, false),
+
+        ExecuteMsg::SetReservationTerms {
+            terms
+        }=> set_reservation_terms(deps, _env, info, terms),
+
+        ExecuteMsg::ReserveLandNft {
+            for_key, proof, allocation
+        }=> reserve_land_nft(deps, _env, info, for_key, proof, allocation),
+
+        ExecuteMsg::ReleaseReservation {
+            for_key
+        }=> release_reservation(deps, _env, info, for_key),
+
+        ExecuteMsg::SetPaymentPlan {
+            for_key, plan
+        }=> set_payment_plan(deps, _env, info, for_key, plan),
+
+        ExecuteMsg::StartPaymentPlan {
+            for_key, proof, allocation
+        }=> start_payment_plan(deps, _env, info, for_key, proof, allocation),
+
+        ExecuteMsg::PayInstallment {
+            token_id
+        }=> pay_installment(deps, _env, info, token_id),
+
+        ExecuteMsg::RepossessLandNft {
+            token_id
+        }=> repossess_land_nft(deps, _env, info, token_id),
+
+        ExecuteMsg::SetCw20Price {
+            for_key, contract, price
+        }=> set_cw20_price(deps, _env, info, for_key, contract, price),
+
+        ExecuteMsg::Receive(wrapper) => receive_cw20(deps, _env, info, wrapper),
+
+        ExecuteMsg::BurnLandNft {
+            token_id
+        }=> burn_land_nft(deps, _env, info, token_id),
+
+        ExecuteMsg::AddAdmin {
+            address
+        }=> add_admin(deps, _env, info, address),
+
+        ExecuteMsg::RemoveAdmin {
+            address
+        }=> remove_admin(deps, _env, info, address),
+
+        ExecuteMsg::UpdateConfig {
+            name, symbol, price_denom, external_url_prefix, minter, max_royalty_bps
+        }=> update_config(deps, _env, info, name, symbol, price_denom, external_url_prefix, minter, max_royalty_bps),
+
+        ExecuteMsg::SetTreasuries {
+            treasuries
+        }=> set_treasuries(deps, _env, info, treasuries),
+
+        ExecuteMsg::Withdraw {
+            denom, amount
+        }=> withdraw(deps, _env, info, denom, amount),
+
+        ExecuteMsg::GrantRole {
+            address, role
+        }=> grant_role(deps, _env, info, address, role),
+
+        ExecuteMsg::RevokeRole {
+            address, role
+        }=> revoke_role(deps, _env, info, address, role),
     }
 }
 
@@ -122,9 +322,57 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetAllLandNftsBy { status, start_after, limit} =>
         to_binary(&get_all_land_nfts_by(deps, status, start_after, limit)?),
 
+        QueryMsg::GetAllLandNftsByOwner { owner, start_after, limit} =>
+        to_binary(&get_all_land_nfts_by_owner(deps, owner, start_after, limit)?),
+
+        QueryMsg::GetAllLandNftsByDenom { price_denom, start_after, limit} =>
+        to_binary(&get_all_land_nfts_by_denom(deps, price_denom, start_after, limit)?),
+
         QueryMsg::GetCountOfLandNftsBy { status } =>
         to_binary(&land_nfts_count_by(deps, status)?),
 
+        QueryMsg::Extension { msg } => match msg {
+
+            Cw2981QueryMsg::RoyaltyInfo { token_id, sale_price } =>
+            to_binary(&get_cw2981_royalty_info(deps, token_id, sale_price)?),
+
+            Cw2981QueryMsg::CheckRoyalties {} =>
+            to_binary(&check_royalties()?),
+        },
+
+        QueryMsg::Listing { token_id } =>
+        to_binary(&get_listing(deps, token_id)?),
+
+        QueryMsg::Auction { for_key } =>
+        to_binary(&get_auction(deps, for_key)?),
+
+        QueryMsg::Reservation { for_key } =>
+        to_binary(&get_reservation(deps, for_key)?),
+
+        QueryMsg::Cw20Prices { for_key } =>
+        to_binary(&get_cw20_prices(deps, for_key)?),
+
+        QueryMsg::PaymentPlan { for_key } =>
+        to_binary(&get_payment_plan(deps, for_key)?),
+
+        QueryMsg::InstallmentPlan { token_id } =>
+        to_binary(&get_installment_plan(deps, token_id)?),
+
+        QueryMsg::CurrentPrice { for_key } =>
+        to_binary(&get_current_price(deps, _env, for_key)?),
+
+        QueryMsg::MintEligibility { address } =>
+        to_binary(&get_mint_eligibility(deps, _env, address)?),
+
+        QueryMsg::Listings { denom, min_price, max_price, start_after, limit } =>
+        to_binary(&get_listings(deps, denom, min_price, max_price, start_after, limit)?),
+
+        QueryMsg::RoyaltyInfo { token_id, sale_price } =>
+        to_binary(&get_royalty_info(deps, token_id, sale_price)?),
+
+        QueryMsg::CatalogStats {} =>
+        to_binary(&get_catalog_stats(deps)?),
+
         QueryMsg::GetLandNftByIndex { status, index} =>
         to_binary(&get_by_index_in(deps, status, index)?),
 
@@ -137,6 +385,9 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetLandNft { key } => 
         to_binary( &get_land_nft(deps, key)?),
 
+        QueryMsg::RemainingPlots { for_key } => 
+        to_binary( &get_remaining_plots(deps, for_key)?),
+
         QueryMsg::AllMintedTokens { start_after , limit } =>
         get_all_minted_tokens(deps, _env, start_after, limit),
 
@@ -148,6 +399,16 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::NftInfo { token_id }=> get_nft_info(deps, _env, token_id),
         
         QueryMsg::AllNftInfo { token_id} => get_all_nft_info(deps, _env, token_id),
+
+        QueryMsg::ListAdmins {} => to_binary(&list_admins(deps)?),
+
+        QueryMsg::Config {} => to_binary(&get_config(deps)?),
+
+        QueryMsg::Treasuries {} => to_binary(&get_treasuries(deps)?),
+
+        QueryMsg::PendingBalance { address } => to_binary(&get_pending_balance(deps, address)?),
+
+        QueryMsg::RolesOf { address } => to_binary(&roles_of(deps, address)?),
     
     }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,11 +6,161 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Cw721(#[from] cw721_base::ContractError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
-    #[error("InvalidLandNft")]
-    InvalidLandNft {},
+    #[error("CannotRemoveLastAdmin")]
+    CannotRemoveLastAdmin {},
+
+    #[error("InvalidRole {role}")]
+    InvalidRole { role : u8 },
+
+    #[error("InvalidTreasuries")]
+    InvalidTreasuries {},
+
+    #[error("CollectionAlreadyInitialized")]
+    CollectionAlreadyInitialized {},
+
+    #[error("InsufficientFunds {required}{denom}")]
+    InsufficientFunds { required : Uint128, denom : String },
+
+    #[error("WrongDenom, expected {expected}")]
+    WrongDenom { expected : String },
+
+    #[error("InsufficientPendingBalance, available {available}{denom}")]
+    InsufficientPendingBalance { available : Uint128, denom : String },
+
+    #[error("PlotNotAvailable {token_id}")]
+    PlotNotAvailable { token_id : String },
+
+    #[error("LandNftHasLiveToken {key}")]
+    LandNftHasLiveToken { key : String },
+
+    #[error("LandNftNotFound {key}")]
+    LandNftNotFound { key : String },
+
+    #[error("AlreadyMinted {key}")]
+    AlreadyMinted { key : String },
+
+    #[error("DuplicateKey {key}")]
+    DuplicateKey { key : String },
+
+    #[error("NotEnoughPlots {key}")]
+    NotEnoughPlots { key : String },
+
+    #[error("PlotsAlreadyMinted {key}, minted {minted}")]
+    PlotsAlreadyMinted { key : String, minted : u16 },
+
+    #[error("TokenNotFound {token_id}")]
+    TokenNotFound { token_id : String },
+
+    #[error("InvalidAddress {address}")]
+    InvalidAddress { address : String },
+
+    #[error("NotAnAdmin {address}")]
+    NotAnAdmin { address : String },
+
+    #[error("InvalidRoyalty {royalty}")]
+    InvalidRoyalty { royalty : u16 },
+
+    #[error("RoyaltyTotalExceeded {total}, max {max}")]
+    RoyaltyTotalExceeded { total : u32, max : u16 },
+
+    #[error("DuplicateRoyaltyIndex {index}")]
+    DuplicateRoyaltyIndex { index : u8 },
+
+    #[error("DuplicateRoyaltyCreator {address}")]
+    DuplicateRoyaltyCreator { address : String },
+
+    #[error("RoyaltyNotFound {address}")]
+    RoyaltyNotFound { address : String },
+
+    #[error("InvalidPrice")]
+    InvalidPrice {},
+
+    #[error("ListingNotFound {token_id}")]
+    ListingNotFound { token_id : String },
+
+    #[error("ListingExpired {token_id}")]
+    ListingExpired { token_id : String },
+
+    #[error("InvalidAuction")]
+    InvalidAuction {},
+
+    #[error("AuctionNotFound {key}")]
+    AuctionNotFound { key : String },
+
+    #[error("AuctionActive {key}")]
+    AuctionActive { key : String },
+
+    #[error("AuctionNotOpen {key}")]
+    AuctionNotOpen { key : String },
+
+    #[error("AuctionNotEnded {key}")]
+    AuctionNotEnded { key : String },
+
+    #[error("BidTooLow, minimum {minimum}{denom}")]
+    BidTooLow { minimum : Uint128, denom : String },
+
+    #[error("InvalidDutchAuction")]
+    InvalidDutchAuction {},
+
+    #[error("InvalidSalePhases")]
+    InvalidSalePhases {},
+
+    #[error("SalePhaseNotFound {name}")]
+    SalePhaseNotFound { name : String },
+
+    #[error("NoActiveSalePhase")]
+    NoActiveSalePhase {},
+
+    #[error("NotAllowlisted {phase}")]
+    NotAllowlisted { phase : String },
+
+    #[error("MintCapReached {phase}, cap {cap}")]
+    MintCapReached { phase : String, cap : u32 },
+
+    #[error("InvalidMerkleProof {phase}")]
+    InvalidMerkleProof { phase : String },
+
+    #[error("InvalidReservationTerms")]
+    InvalidReservationTerms {},
+
+    #[error("ReservationsDisabled")]
+    ReservationsDisabled {},
+
+    #[error("LandNftReserved {key}")]
+    LandNftReserved { key : String },
+
+    #[error("ReservationNotFound {key}")]
+    ReservationNotFound { key : String },
+
+    #[error("ReservationNotExpired {key}")]
+    ReservationNotExpired { key : String },
+
+    #[error("ReservationMultiplePlots {key}")]
+    ReservationMultiplePlots { key : String },
+
+    #[error("InvalidPaymentPlan")]
+    InvalidPaymentPlan {},
+
+    #[error("PaymentPlanNotFound {key}")]
+    PaymentPlanNotFound { key : String },
+
+    #[error("InstallmentPlanNotFound {token_id}")]
+    InstallmentPlanNotFound { token_id : String },
+
+    #[error("InstallmentNotOverdue {token_id}")]
+    InstallmentNotOverdue { token_id : String },
+
+    #[error("Cw20NotAccepted {contract}")]
+    Cw20NotAccepted { contract : String },
+
+    #[error("EmptyBatch")]
+    EmptyBatch {},
 
     #[error("CustomError")]
     CustomError { error : StdError },
@@ -1,74 +1,112 @@
 use crate::resp::{LandNftMediaTypesResponse, LandNftRoyaltiesResponse, 
-    LandNftsResponse, LandNftResponse, LandNftCountResponse, OptionalLandNftResponse};
-use cosmwasm_std::{Deps, Env, StdResult, Order, Binary};
-use crate::state::{LAND_NFTS, LandNftMediaType, LandNftRoyalty, LandNft};
+    LandNftsResponse, LandNftResponse, LandNftCountResponse, OptionalLandNftResponse, AdminsResponse, RolesResponse, ConfigResponse, TreasuriesResponse, PendingBalanceResponse, RemainingPlotsResponse, 
+    CatalogStatsResponse, StatusCount, RoyaltyInfoResponse, RoyaltyPayout, 
+    RoyaltiesInfoResponse, CheckRoyaltiesResponse, ListingResponse, ListingsResponse, AuctionResponse, CurrentPriceResponse, MintEligibilityResponse, ReservationResponse, Cw20PricesResponse, PaymentPlanResponse, InstallmentPlanResponse};
+use cosmwasm_std::{Addr, Deps, Env, StdError, StdResult, Uint128, Order, Binary, Coin};
+use cw20::Cw20CoinVerified;
+use crate::state::{land_nfts, listings, Listing, AUCTIONS, land_nft_price, royalty_amount, RESERVATIONS, CW20_PRICES, PAYMENT_PLANS, INSTALLMENT_PLANS, ROLE_MINTER, ALLOWLIST, PHASE_MINTS, land_nft_status_key, STATUS_COUNTS, TOTAL_AREA, LISTED_VALUES, LandNftMediaType, LandNftRoyalty, LandNft, ADMINS, ROLES, ROLE_SUPER_ADMIN, CONFIG, PENDING_BALANCES};
 use cw_storage_plus::Bound;
 use std::convert::TryFrom;
 
+/// Loads the land nft, a missing key is reported as not found
+fn find_land_nft(deps: Deps, _key : &str) -> StdResult<LandNft> {
+
+    land_nfts().may_load(deps.storage, _key)?
+    .ok_or_else(|| StdError::not_found(format!("LandNft {}", _key)))
+}
+
 pub fn get_land_nft(deps: Deps, _key : String ) -> StdResult<LandNftResponse>{
 
-    let stored_land = LAND_NFTS.key(_key.as_str());
+    let land_nft = find_land_nft(deps, _key.as_str())?;
     
-    let land_nft = stored_land.may_load(deps.storage).expect("Failed to find land nft").expect(
-        format!("Failed to unwrap, key not found :\"{}\"", _key).as_str());
-    
-    Ok (LandNftResponse { land_nft :land_nft })
+    Ok (LandNftResponse { land_nft })
 }
 
-pub fn get_land_nft_royalties (deps: Deps, _key : String ) -> StdResult<LandNftRoyaltiesResponse>{
+pub fn get_remaining_plots(deps: Deps, _key : String ) -> StdResult<RemainingPlotsResponse>{
 
-    let stored_land = LAND_NFTS.key(_key.as_str());
-    
-    let land_nft = stored_land.may_load(deps.storage).expect("Failed to find land nft").unwrap();
+    let land_nft = get_land_nft(deps, _key.clone())?.land_nft;
 
-    let royalties = land_nft.all_royalties();
+    Ok (RemainingPlotsResponse { 
+        for_key : _key,
+        total_plots : land_nft.plot_count(),
+        minted_plots : land_nft.minted_plot_count(),
+        remaining_plots : land_nft.remaining_plot_count(),
+    })
+}
 
-    let mut return_royalties : Vec<LandNftRoyalty> = Vec::new();
+pub fn get_land_nft_royalties (deps: Deps, _key : String ) -> StdResult<LandNftRoyaltiesResponse>{
 
-    if royalties.is_some() {
+    let land_nft = find_land_nft(deps, _key.as_str())?;
 
-        return_royalties = royalties.unwrap();
-    }
+    let return_royalties : Vec<LandNftRoyalty> = land_nft.all_royalties().unwrap_or_default();
 
     Ok (LandNftRoyaltiesResponse { royalties : return_royalties })
 
 }
 
 
-pub fn get_all_land_nft_media_types (deps: Deps,  _env : Env, _key : String ) -> StdResult<LandNftMediaTypesResponse>{
+/// Breaks down the royalties owed on a sale of the token, 
+/// the token id may be a plot token or the land nft's key
+pub fn get_royalty_info(deps: Deps, token_id : String, sale_price : Uint128) -> StdResult<RoyaltyInfoResponse>{
 
-    let stored_land = LAND_NFTS.key(_key.as_str());
-    
-    let land_nft = stored_land.may_load(deps.storage).expect("Failed to find land nft").unwrap();
+    let key = LandNft::parse_token_id(token_id.as_str()).map(|(k, _)| k).unwrap_or_else(|| token_id.clone());
+
+    let land_nft = find_land_nft(deps, key.as_str())?;
 
-    let media_types = land_nft.all_media_types();
+    let payouts = land_nft.royalty_shares(sale_price).into_iter().map(|(r, amount)| RoyaltyPayout {
+        creator_wallet : r.creator_wallet,
+        royalty : r.royalty,
+        amount,
+    }).collect::<Vec<RoyaltyPayout>>();
 
-    let mut return_media_types : Vec<LandNftMediaType> = Vec::new();
+    Ok(RoyaltyInfoResponse {
+        token_id,
+        sale_price,
+        total_amount : payouts.iter().map(|p| p.amount).sum(),
+        payouts,
+    })
+}
 
-    if media_types.is_some() {
 
-        return_media_types = media_types.unwrap();
-    }
+/// Answers the cw2981 royalty info from the royalty fields stored on the token when minted, 
+/// the whole amount goes to the royalty payment address
+pub fn get_cw2981_royalty_info(deps: Deps, token_id : String, sale_price : Uint128) -> StdResult<RoyaltiesInfoResponse>{
 
-    Ok (LandNftMediaTypesResponse { media_types : return_media_types})
+    let token = crate::ins::MyNftMintingContract::default().tokens.load(deps.storage, token_id.as_str())?;
 
+    let metadata = token.extension.unwrap_or_default();
+
+    let address = metadata.royalty_payment_address.unwrap_or_default();
+
+    let royalty_amount : Uint128 = match metadata.royalty_bps {
+        Some(royalty_bps) if !address.is_empty() => royalty_amount(sale_price, royalty_bps),
+        _ => Uint128::zero(),
+    };
+
+    Ok(RoyaltiesInfoResponse {
+        address,
+        royalty_amount,
+    })
 }
 
 
-pub fn get_land_nft_media_types (deps: Deps,  _key : String, media_type : u8 ) -> StdResult<LandNftMediaTypesResponse>{
+pub fn get_all_land_nft_media_types (deps: Deps,  _env : Env, _key : String ) -> StdResult<LandNftMediaTypesResponse>{
 
-    let stored_land = LAND_NFTS.key(_key.as_str());
-    
-    let land_nft = stored_land.may_load(deps.storage).expect("Failed to find land nft").unwrap();
+    let land_nft = find_land_nft(deps, _key.as_str())?;
 
-    let media_types = land_nft.all_media_types();
+    let return_media_types : Vec<LandNftMediaType> = land_nft.all_media_types().unwrap_or_default();
 
-    let mut return_media_types : Vec<LandNftMediaType> = vec![];
+    Ok (LandNftMediaTypesResponse { media_types : return_media_types})
 
-    if media_types.is_some() {
+}
 
-        return_media_types = media_types.unwrap().into_iter().filter(|mt| mt.media_type == media_type).collect::<Vec<LandNftMediaType>>();
-    }
+
+pub fn get_land_nft_media_types (deps: Deps,  _key : String, media_type : u8 ) -> StdResult<LandNftMediaTypesResponse>{
+
+    let land_nft = find_land_nft(deps, _key.as_str())?;
+
+    let return_media_types : Vec<LandNftMediaType> = land_nft.all_media_types().unwrap_or_default()
+    .into_iter().filter(|mt| mt.media_type == media_type).collect::<Vec<LandNftMediaType>>();
 
     Ok (LandNftMediaTypesResponse { media_types : return_media_types})
 
@@ -80,44 +118,35 @@ pub const DEFAULT_LIMIT : u32 = 10;
 pub const MAX_LIMIT : u32 = 20;
 
 
+/// Collects a page of land nfts from a range over the map or one of its indexes,
+/// the primary key is put back into each land nft's key
+fn collect_land_nfts<'c>(range : Box<dyn Iterator<Item = StdResult<(Vec<u8>, LandNft)>> + 'c>, 
+    limit : usize) -> StdResult<Vec<LandNft>> {
+
+    range
+    .take(limit)
+    .map(|itm| {
+        
+        let (_k, mut v) = itm?;
+
+        v.key = Some(String::from_utf8(_k)?);
+
+        Ok(v)
+    }).collect()
+}
+
+
 pub fn get_all_land_nfts(deps : Deps , start_after: Option<String>, limit: Option<u32>) 
 ->StdResult<LandNftsResponse> {
 
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start = start_after.map(Bound::exclusive);
 
-    let land_nfts : StdResult <Vec<LandNft>> = 
-    LAND_NFTS
-    .range(deps.storage, start, None, Order::Ascending)
-    .take(limit)
-    .map(|itm| {
-        
-        let (_k, v) = itm?;
-
-        Ok(LandNft {
-            key : Some(String::from_utf8(_k)?),
-            owner : v.owner,
-            name : v.name, 
-            description : v.description, 
-            total_size : v.total_size,
-            each_size : v.each_size,
-            size_unit : v.size_unit, 
-            addr : v.addr,
-            total_lands : v.total_lands, 
-            price : v.price, 
-            price_denom : v.price_denom, 
-            status : v.status, 
-            symbol : v.symbol, 
-            media_types : v.media_types,
-            royalties : v.royalties,
-            other_attributes : v.other_attributes, 
-            date_created : v.date_created,
-            date_updated : v.date_updated, 
-        })
-    }).collect();
+    let land_nfts = collect_land_nfts(
+        land_nfts().range(deps.storage, start, None, Order::Ascending), limit)?;
 
     Ok(LandNftsResponse {
-        land_nfts: land_nfts?,
+        land_nfts,
     })
 }
 
@@ -125,85 +154,101 @@ pub fn get_all_land_nfts(deps : Deps , start_after: Option<String>, limit: Optio
 pub fn get_all_land_nfts_by(deps : Deps , status : Option<u8>,
 start_after: Option<String>, limit: Option<u32>) ->StdResult<LandNftsResponse> {
 
-    
-    let resp : StdResult<LandNftsResponse> = get_all_land_nfts(deps, start_after, limit);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
 
-    match resp {
+    let land_nfts = collect_land_nfts(
+        land_nfts().idx.status.prefix(land_nft_status_key(status))
+        .range(deps.storage, start, None, Order::Ascending), limit)?;
 
-        Ok(r) =>{
+    Ok(LandNftsResponse {
+        land_nfts,
+    })
+}
 
-            let land_nfts : Vec<LandNft> = r.land_nfts;
-            let _filtered = land_nfts.iter().filter (|l| {
 
-                if status.is_none() {
+pub fn get_all_land_nfts_by_owner(deps : Deps , owner : String,
+start_after: Option<String>, limit: Option<u32>) ->StdResult<LandNftsResponse> {
 
-                    l.status == None 
-                }
-                else {
+    let owner = deps.api.addr_validate(owner.as_str())?;
 
-                    l.status == status 
-                }
-            }).cloned().collect::<Vec<LandNft>>();
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
 
-        
-            Ok(LandNftsResponse{land_nfts:_filtered})
+    let land_nfts = collect_land_nfts(
+        land_nfts().idx.owner.prefix(owner)
+        .range(deps.storage, start, None, Order::Ascending), limit)?;
 
-        },
+    Ok(LandNftsResponse {
+        land_nfts,
+    })
+}
 
-        Err(_)=>{
 
-            Ok(LandNftsResponse{land_nfts: vec![]})
-        },
-    }
+pub fn get_all_land_nfts_by_denom(deps : Deps , price_denom : String,
+start_after: Option<String>, limit: Option<u32>) ->StdResult<LandNftsResponse> {
 
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let land_nfts = collect_land_nfts(
+        land_nfts().idx.price_denom.prefix(price_denom.into_bytes())
+        .range(deps.storage, start, None, Order::Ascending), limit)?;
+
+    Ok(LandNftsResponse {
+        land_nfts,
+    })
 }
 
 
 pub fn land_nfts_count_by(deps : Deps , status : Option<u8>)
 ->StdResult<LandNftCountResponse>{
 
-    let resp : StdResult<LandNftsResponse> = get_all_land_nfts_by(deps, status, None, None);
+    let count = STATUS_COUNTS.may_load(deps.storage, land_nft_status_key(status))?.unwrap_or(0);
 
-    match resp {
-        Ok(r) =>{
+    Ok(LandNftCountResponse{ count : count as usize })
+}
 
-            Ok(LandNftCountResponse{ count :r.land_nfts.len() })
-        },
 
-        Err(_)=>{
+pub fn get_catalog_stats(deps : Deps) -> StdResult<CatalogStatsResponse> {
 
-            Ok(LandNftCountResponse{ count : 0 })
-        }
-    }
-}
+    let by_status : StdResult<Vec<StatusCount>> = STATUS_COUNTS
+    .range(deps.storage, None, None, Order::Ascending)
+    .map(|itm| {
 
+        let (k, count) = itm?;
 
-pub fn get_by_index_in(deps : Deps, status : Option<u8>, index : u32) -> StdResult<OptionalLandNftResponse>{
+        Ok(StatusCount { status : k.first().copied().unwrap_or(0), count })
+    }).collect();
 
-    let resp : StdResult<LandNftsResponse> = get_all_land_nfts_by(deps, status, None, None);
+    let by_status = by_status?.into_iter().filter(|s| s.count > 0).collect::<Vec<StatusCount>>();
 
-    let mut land_nft : Option<LandNft> = None;
+    let listed_values : StdResult<Vec<Coin>> = LISTED_VALUES
+    .range(deps.storage, None, None, Order::Ascending)
+    .map(|itm| {
 
-    match resp {
-        Ok(r) =>{
+        let (denom, amount) = itm?;
 
-            for (pos, e) in r.land_nfts.iter().enumerate() {
-                
-                if pos == usize::try_from(index).unwrap_or(0) {
+        Ok(Coin { denom : String::from_utf8(denom)?, amount })
+    }).collect();
 
-                    land_nft = Some(e.clone()); 
-                    break;
-                }
-            }
-        },
+    Ok(CatalogStatsResponse {
+        total : by_status.iter().map(|s| s.count).sum(),
+        by_status,
+        total_area : TOTAL_AREA.may_load(deps.storage)?.unwrap_or(0),
+        listed_values : listed_values?.into_iter().filter(|c| !c.amount.is_zero()).collect(),
+    })
+}
 
-        Err(_)=>{
 
-            land_nft = None
-        }
-    }
+pub fn get_by_index_in(deps : Deps, status : Option<u8>, index : u32) -> StdResult<OptionalLandNftResponse>{
+
+    let land_nft = collect_land_nfts(
+        Box::new(land_nfts().idx.status.prefix(land_nft_status_key(status))
+        .range(deps.storage, None, None, Order::Ascending)
+        .skip(usize::try_from(index).unwrap_or(0))), 1)?.pop();
 
-    Ok(OptionalLandNftResponse{ land_nft : land_nft})
+    Ok(OptionalLandNftResponse{ land_nft })
 
 }
 
@@ -212,8 +257,8 @@ pub fn get_all_minted_tokens(deps : Deps ,  _env : Env,
 
 
     let all_tokens_msg = cw721_base::msg::QueryMsg::AllTokens {
-        start_after : start_after,
-        limit : limit,
+        start_after,
+        limit,
     };
 
     crate::ins::MyNftMintingContract::default().query(deps, _env, all_tokens_msg )
@@ -231,9 +276,9 @@ pub fn get_minted_tokens_by_owner( deps: Deps, _env : Env, owner : String ,
     start_after: Option<String>, limit: Option<u32>) -> StdResult<Binary> {
 
     let msg = cw721_base::msg::QueryMsg::Tokens {
-        owner : owner ,
-        start_after : start_after,
-        limit : limit,
+        owner,
+        start_after,
+        limit,
     };
 
     crate::ins::MyNftMintingContract::default().query(deps, _env, msg)
@@ -244,7 +289,7 @@ pub fn get_nft_info( deps : Deps,env : Env, token_id : String ) -> StdResult<Bin
 
     let msg = cw721_base::msg::QueryMsg::NftInfo {
 
-        token_id : token_id
+        token_id
     };
     crate::ins::MyNftMintingContract::default().query(deps, env, msg)
 }
@@ -253,8 +298,196 @@ pub fn get_all_nft_info( deps : Deps,env : Env, token_id : String ) -> StdResult
 
     let msg = cw721_base::msg::QueryMsg::AllNftInfo {
 
-        token_id : token_id,
+        token_id,
         include_expired : None, 
     };
     crate::ins::MyNftMintingContract::default().query(deps, env, msg)
 }
+
+pub fn list_admins(deps : Deps) -> StdResult<AdminsResponse> {
+
+    let admins = ADMINS.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(AdminsResponse { admins })
+}
+
+pub fn roles_of(deps : Deps, address : String) -> StdResult<RolesResponse> {
+
+    let addr = deps.api.addr_validate(address.as_str())?;
+
+    let mut roles : Vec<u8> = vec![];
+
+    let admins = ADMINS.may_load(deps.storage)?.unwrap_or_default();
+
+    if admins.contains(&addr) {
+        roles.push(ROLE_SUPER_ADMIN);
+    }
+
+    roles.extend(ROLES.may_load(deps.storage, &addr)?.unwrap_or_default());
+
+    Ok(RolesResponse { address : addr, roles })
+}
+
+pub fn get_config(deps : Deps) -> StdResult<ConfigResponse> {
+
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse { config })
+}
+
+pub fn get_treasuries(deps : Deps) -> StdResult<TreasuriesResponse> {
+
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(TreasuriesResponse { treasuries : config.treasuries })
+}
+
+pub fn get_pending_balance(deps : Deps, address : String) -> StdResult<PendingBalanceResponse> {
+
+    let addr = deps.api.addr_validate(address.as_str())?;
+
+    let balances : StdResult<Vec<Coin>> = PENDING_BALANCES
+    .prefix(&addr)
+    .range(deps.storage, None, None, Order::Ascending)
+    .map(|itm| {
+
+        let (denom, amount) = itm?;
+
+        Ok(Coin { denom : String::from_utf8(denom)?, amount })
+    }).collect();
+
+    Ok(PendingBalanceResponse { address : addr, balances : balances? })
+}
+
+pub fn check_royalties() -> StdResult<CheckRoyaltiesResponse> {
+
+    Ok(CheckRoyaltiesResponse { royalty_payments : true })
+}
+
+pub fn get_listing(deps : Deps, token_id : String) -> StdResult<ListingResponse> {
+
+    Ok(ListingResponse { listing : listings().may_load(deps.storage, token_id.as_str())? })
+}
+
+/// Pages through the listings, optionally of a single denom, 
+/// skipping the ones outside of the price range
+pub fn get_listings(deps : Deps, denom : Option<String>, min_price : Option<Uint128>, max_price : Option<Uint128>,
+    start_after: Option<String>, limit: Option<u32>) -> StdResult<ListingsResponse> {
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let range : Box<dyn Iterator<Item = StdResult<(Vec<u8>, Listing)>>> = match denom {
+
+        Some(denom) => listings().idx.denom.prefix(denom.into_bytes())
+        .range(deps.storage, start, None, Order::Ascending),
+
+        None => listings().range(deps.storage, start, None, Order::Ascending),
+    };
+
+    let listings : StdResult<Vec<Listing>> = range
+    .filter(|itm| match itm {
+
+        Ok((_, l)) => min_price.map(|p| l.price >= p).unwrap_or(true) && 
+            max_price.map(|p| l.price <= p).unwrap_or(true),
+
+        Err(_) => true,
+    })
+    .take(limit)
+    .map(|itm| Ok(itm?.1))
+    .collect();
+
+    Ok(ListingsResponse { listings : listings? })
+}
+
+pub fn get_auction(deps : Deps, for_key : String) -> StdResult<AuctionResponse> {
+
+    Ok(AuctionResponse { auction : AUCTIONS.may_load(deps.storage, for_key.as_str())? })
+}
+
+pub fn get_reservation(deps : Deps, for_key : String) -> StdResult<ReservationResponse> {
+
+    Ok(ReservationResponse { reservation : RESERVATIONS.may_load(deps.storage, for_key.as_str())? })
+}
+
+pub fn get_cw20_prices(deps : Deps, for_key : String) -> StdResult<Cw20PricesResponse> {
+
+    let prices : StdResult<Vec<Cw20CoinVerified>> = CW20_PRICES
+    .prefix(for_key.as_str())
+    .range(deps.storage, None, None, Order::Ascending)
+    .map(|itm| {
+
+        let (contract, price) = itm?;
+
+        Ok(Cw20CoinVerified { address : Addr::unchecked(String::from_utf8(contract)?), amount : price })
+    }).collect();
+
+    Ok(Cw20PricesResponse { for_key, prices : prices? })
+}
+
+pub fn get_payment_plan(deps : Deps, for_key : String) -> StdResult<PaymentPlanResponse> {
+
+    Ok(PaymentPlanResponse { plan : PAYMENT_PLANS.may_load(deps.storage, for_key.as_str())? })
+}
+
+pub fn get_installment_plan(deps : Deps, token_id : String) -> StdResult<InstallmentPlanResponse> {
+
+    let plan = INSTALLMENT_PLANS.may_load(deps.storage, token_id.as_str())?;
+
+    Ok(InstallmentPlanResponse { 
+        next_due : plan.as_ref().filter(|p| p.next_amount().is_some()).map(|p| p.next_due()),
+        plan,
+    })
+}
+
+pub fn get_current_price(deps : Deps, env : Env, for_key : String) -> StdResult<CurrentPriceResponse> {
+
+    let land_nft = find_land_nft(deps, for_key.as_str())?;
+
+    let denom = match land_nft.price_denom.clone() {
+
+        Some(d) => d,
+
+        None => CONFIG.load(deps.storage)?.price_denom,
+    };
+
+    Ok(CurrentPriceResponse { 
+        for_key,
+        price : Coin { denom, amount : land_nft_price(deps.storage, &land_nft, env.block.time)? },
+    })
+}
+
+/// Tells whether the address can mint right now, and how many plots it has left in the active phase
+pub fn get_mint_eligibility(deps : Deps, env : Env, address : String) -> StdResult<MintEligibilityResponse> {
+
+    let addr = deps.api.addr_validate(address.as_str())?;
+
+    let mut resp = MintEligibilityResponse { address : addr.clone(), eligible : false, 
+        phase : None, minted : 0, remaining : None, price : None };
+
+    if crate::ins::has_role(deps, &addr, ROLE_MINTER) {
+
+        resp.eligible = true;
+        return Ok(resp);
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    if let Some(phase) = config.active_sale_phase(env.block.time) {
+
+        let minted = PHASE_MINTS.may_load(deps.storage, (phase.name.as_str(), &addr))?.unwrap_or(0);
+        let remaining = phase.mint_cap.map(|cap| cap.saturating_sub(minted));
+
+        // a merkle allowlist needs the address's proof, which the query doesn't have
+        let allowed = !phase.has_allowlist || (phase.merkle_root.is_none() 
+            && ALLOWLIST.has(deps.storage, (phase.name.as_str(), &addr)));
+
+        resp.eligible = allowed && remaining.map(|r| r > 0).unwrap_or(true);
+        resp.phase = Some(phase.name.clone());
+        resp.minted = minted;
+        resp.remaining = remaining;
+        resp.price = phase.price;
+    }
+
+    Ok(resp)
+}
@@ -1,28 +1,280 @@
-use cosmwasm_std::{DepsMut, Env, Response, Addr, MessageInfo, Empty, coins, BankMsg};
+use cosmwasm_std::{Api, Deps, DepsMut, Env, Response, Addr, MessageInfo, Empty, Storage, BankMsg, Coin, CosmosMsg, WasmMsg, 
+    StdError, StdResult, Timestamp, Uint128, to_binary, from_binary};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw721::{ContractInfoResponse, Expiration};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use crate::error::ContractError;
-use crate::state::{LAND_NFTS, LandNft, LandNftMediaType, LandNftRoyalty, LAND_NFT_COUNTER, IndexCounter, Treasury};
-use std::convert::{TryFrom};
+use crate::msg::{TreasuryMsg, Cw20HookMsg};
+use crate::merkle;
+use crate::state::{land_nfts, save_land_nft, remove_land_nft_entry, LandNft, LandNftMediaType, LandNftRoyalty, LAND_NFT_COUNTER, IndexCounter, Treasury, TREASURY_TOTAL_BPS, ADMINS,
+    ROLES, ROLE_SUPER_ADMIN, ROLE_CATALOG_EDITOR, ROLE_MINTER, ROLE_TREASURER, is_valid_role, CONFIG, Config, PENDING_BALANCES, LAND_NFT_STATUS_TRANSFERRED, MAX_BASIS_POINTS, 
+    Listing, listings, Auction, AUCTIONS, Bid, AUCTION_TIME_EXTENSION,
+    DutchAuction, DUTCH_AUCTIONS, DEFAULT_DUTCH_AUCTION, land_nft_price, is_valid_dutch_auction_mode,
+    SalePhase, ALLOWLIST, PHASE_MINTS, Reservation, ReservationTerms, RESERVATIONS, LAND_NFT_STATUS_RESERVED,
+    PaymentPlan, PAYMENT_PLANS, InstallmentPlan, INSTALLMENT_PLANS, CW20_PRICES, cw20_denom, cw20_contract};
 
-const ALLOWED_ADMINS : [&'static str; 3] = ["terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9",
-"terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0", "terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3"];
 
+fn is_allowed_admin( deps : Deps, info: MessageInfo ) -> bool{
 
-fn is_allowed_admin( info: MessageInfo ) -> bool{
+    let admins = ADMINS.may_load(deps.storage).unwrap_or(None).unwrap_or_default();
 
-    let admin = info.sender.clone();
-    let mut allowed : bool = false ;
+    admins.contains(&info.sender)
+}
+
+/// Super-admins implicitly hold every role
+pub fn has_role( deps : Deps, addr : &Addr, role : u8 ) -> bool {
+
+    let admins = ADMINS.may_load(deps.storage).unwrap_or(None).unwrap_or_default();
+
+    if admins.contains(addr) {
+        return true;
+    }
+
+    if role == ROLE_SUPER_ADMIN {
+        return false;
+    }
+
+    let roles = ROLES.may_load(deps.storage, addr).unwrap_or(None).unwrap_or_default();
+
+    roles.contains(&role)
+}
+
+
+pub fn add_admin(deps: DepsMut, _env : Env, 
+    info: MessageInfo, address : String) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let admin = validate_address(deps.api, address.as_str())?;
+
+    let mut admins = ADMINS.may_load(deps.storage)?.unwrap_or_default();
+
+    if !admins.contains(&admin) {
+        admins.push(admin);
+    }
+
+    ADMINS.save(deps.storage, &admins)?;
+
+    Ok(Response::new().add_attribute("method", "add_admin").add_attribute("admin", address))
+}
+
+pub fn remove_admin(deps: DepsMut, _env : Env, 
+    info: MessageInfo, address : String) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let admin = validate_address(deps.api, address.as_str())?;
+
+    let mut admins = ADMINS.may_load(deps.storage)?.unwrap_or_default();
+
+    if !admins.contains(&admin) {
+        return Err(ContractError::NotAnAdmin { address });
+    }
+
+    if admins.len() == 1 {
+        return Err(ContractError::CannotRemoveLastAdmin {});
+    }
+
+    admins.retain(|a| *a != admin);
+
+    ADMINS.save(deps.storage, &admins)?;
+
+    Ok(Response::new().add_attribute("method", "remove_admin").add_attribute("admin", address))
+}
+
+/// Treasury splits must add up to 10000 basis points with exactly one primary 
+/// treasury, unless no treasury is configured
+pub fn validate_treasuries(api : &dyn Api, treasuries : Vec<TreasuryMsg>) -> Result<Vec<Treasury>, ContractError> {
+
+    let mut valid_treasuries : Vec<Treasury> = vec![];
+
+    let mut total : u32 = 0;
+
+    for t in treasuries.iter() {
+
+        let wallet_address = validate_address(api, t.wallet_address.as_str())?;
+
+        total += t.basis_points as u32;
+
+        valid_treasuries.push(Treasury { wallet_address, 
+            basis_points : t.basis_points, is_primary : t.is_primary });
+    }
+
+    // sale proceeds are only ever credited to the treasuries, so at least the primary one is required
+    let primaries = valid_treasuries.iter().filter(|t| t.is_primary).count();
+
+    if total != TREASURY_TOTAL_BPS as u32 || primaries != 1 {
+
+        return Err(ContractError::InvalidTreasuries {});
+    }
+
+    Ok(valid_treasuries)
+}
+
+pub fn set_treasuries(deps: DepsMut, _env : Env, 
+    info: MessageInfo, treasuries : Vec<TreasuryMsg>) -> Result<Response, ContractError> {
+
+    if !has_role(deps.as_ref(), &info.sender, ROLE_TREASURER) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    config.treasuries = validate_treasuries(deps.api, treasuries)?;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("method", "set_treasuries"))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_config(deps: DepsMut, _env : Env, 
+    info: MessageInfo, 
+    name : Option<String>,
+    symbol : Option<String>,
+    price_denom : Option<String>,
+    external_url_prefix : Option<String>,
+    minter : Option<String>,
+    max_royalty_bps : Option<u16>) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
 
-    let admins = ALLOWED_ADMINS.clone();
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    let collection_changed = name.is_some() || symbol.is_some() || minter.is_some();
+
+    if let Some(name) = name {
+        config.name = name;
+    }
+
+    if let Some(symbol) = symbol {
+        config.symbol = symbol;
+    }
 
-    admins.iter().for_each( |a| { 
-        if *a == admin.to_string() {
-            allowed = true ; 
+    if let Some(minter) = minter {
+
+        let minter = validate_address(deps.api, minter.as_str())?;
+
+        // the previous minter loses the role it was granted as minter
+        if minter != config.minter {
+
+            let mut roles = ROLES.may_load(deps.storage, &config.minter)?.unwrap_or_default();
+
+            roles.retain(|r| *r != ROLE_MINTER);
+
+            if roles.is_empty() {
+                ROLES.remove(deps.storage, &config.minter);
+            }
+            else {
+                ROLES.save(deps.storage, &config.minter, &roles)?;
+            }
         }
-    });
 
-    allowed
+        let mut roles = ROLES.may_load(deps.storage, &minter)?.unwrap_or_default();
+        if !roles.contains(&ROLE_MINTER) {
+            roles.push(ROLE_MINTER);
+            ROLES.save(deps.storage, &minter, &roles)?;
+        }
+
+        config.minter = minter;
+    }
+
+    if let Some(price_denom) = price_denom {
+        config.price_denom = price_denom;
+    }
+
+    if let Some(external_url_prefix) = external_url_prefix {
+        config.external_url_prefix = external_url_prefix;
+    }
+
+    if let Some(max_royalty_bps) = max_royalty_bps {
+        config.max_royalty_bps = validate_max_royalty(max_royalty_bps)?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    if collection_changed {
+        save_collection_info(deps.storage, &config)?;
+    }
+
+    Ok(Response::new().add_attribute("method", "update_config"))
+}
+
+pub fn grant_role(deps: DepsMut, _env : Env, 
+    info: MessageInfo, address : String, role : u8) -> Result<Response, ContractError> {
+
+    if !is_valid_role(role) {
+        return Err(ContractError::InvalidRole { role });
+    }
+
+    if role == ROLE_SUPER_ADMIN {
+        return add_admin(deps, _env, info, address);
+    }
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let addr = validate_address(deps.api, address.as_str())?;
+
+    let mut roles = ROLES.may_load(deps.storage, &addr)?.unwrap_or_default();
+
+    if !roles.contains(&role) {
+        roles.push(role);
+    }
+
+    ROLES.save(deps.storage, &addr, &roles)?;
+
+    Ok(Response::new().add_attribute("method", "grant_role")
+    .add_attribute("address", address).add_attribute("role", role.to_string()))
+}
+
+pub fn revoke_role(deps: DepsMut, _env : Env, 
+    info: MessageInfo, address : String, role : u8) -> Result<Response, ContractError> {
+
+    if !is_valid_role(role) {
+        return Err(ContractError::InvalidRole { role });
+    }
+
+    if role == ROLE_SUPER_ADMIN {
+        return remove_admin(deps, _env, info, address);
+    }
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let addr = validate_address(deps.api, address.as_str())?;
+
+    let mut roles = ROLES.may_load(deps.storage, &addr)?.unwrap_or_default();
+
+    roles.retain(|r| *r != role);
+
+    if roles.is_empty() {
+        ROLES.remove(deps.storage, &addr);
+    }
+    else {
+        ROLES.save(deps.storage, &addr, &roles)?;
+    }
+
+    Ok(Response::new().add_attribute("method", "revoke_role")
+    .add_attribute("address", address).add_attribute("role", role.to_string()))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn add_land_nft(deps: DepsMut,  _env : Env, 
     info: MessageInfo,
     name : Option<String>, 
@@ -36,7 +288,7 @@ pub fn add_land_nft(deps: DepsMut,  _env : Env,
     price_denom : Option<String>
     ) -> Result<Response, ContractError> {
    
-    if !is_allowed_admin(info.clone()) {
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
 
         return Err(ContractError::Unauthorized {});
     }    
@@ -67,6 +319,7 @@ pub fn add_land_nft(deps: DepsMut,  _env : Env,
 
 }
 
+#[allow(clippy::too_many_arguments)]
 fn add_land_nft_by_key(_key : String , deps: DepsMut,  _env : Env,  
     owner : Addr, 
     name : Option<String>, 
@@ -79,22 +332,30 @@ fn add_land_nft_by_key(_key : String , deps: DepsMut,  _env : Env,
     price : u64,
     price_denom : Option<String>) -> Result<Response, ContractError> {
    
-    let stored_land = LAND_NFTS.key(_key.as_str());
+    let stored_land = land_nfts().key(_key.as_str());
     
-    let empty = stored_land.may_load(deps.storage)?;
-    assert_eq!(None, empty); // check if it wasn't previosuly added
+    // check if it wasn't previosuly added
+    if stored_land.may_load(deps.storage)?.is_some() {
+
+        return Err(ContractError::DuplicateKey { key : _key });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
 
     let date_created = _env.block.time;
 
-    let new_land = LandNft::new (Some(_key.clone()), name, description, 
+    let mut new_land = LandNft::new (Some(_key.clone()), name, description, 
     owner ,total_size, each_size, size_unit,
-    addr, total_lands, price, price_denom, date_created );
+    addr, total_lands, price, price_denom.or(Some(config.price_denom)), date_created );
 
-    LAND_NFTS.save(deps.storage, _key.as_str(), &new_land)?;
+    new_land.symbol = config.symbol;
+
+    save_land_nft(deps.storage, _key.as_str(), &new_land)?;
 
     Ok(Response::new().add_attribute("key", _key).add_attribute("method", "add_land_nft"))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn update_land_nft(
     deps: DepsMut,  _env : Env,  
     info: MessageInfo,
@@ -110,58 +371,122 @@ pub fn update_land_nft(
     price_denom : Option<String>) -> Result<Response, ContractError> {
    
     
-    if !is_allowed_admin(info.clone()) {
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
 
         return Err(ContractError::Unauthorized {});
     }    
 
-    let owner = info.clone().sender;
-
-    let stored_land = LAND_NFTS.key(for_key.as_str());
+    let stored_land = land_nfts().key(for_key.as_str());
     
-    let _stored_land_nft = stored_land.may_load(deps.storage)?;
-   
-    if _stored_land_nft.is_none() {
+    // only the edited fields change, the owner, royalties, media types 
+    // and minting progress of the land nft are kept
+    let mut land_nft = stored_land.may_load(deps.storage)?
+    .ok_or_else(|| ContractError::LandNftNotFound { key : for_key.clone() })?;
 
-        return Err(ContractError::InvalidLandNft{});
+    if name.is_some() {
+        land_nft.name = name;
     }
 
-    let date_updated = _env.block.time;
+    if description.is_some() {
+        land_nft.description = description;
+    }
 
-    let new_land = LandNft::new ( 
-    Some(for_key.clone()), name, description, 
-    owner ,total_size, each_size, size_unit,
-    addr, total_lands, price, price_denom, date_updated );
+    if size_unit.is_some() {
+        land_nft.size_unit = size_unit;
+    }
+
+    if price_denom.is_some() {
+        land_nft.price_denom = price_denom;
+    }
 
+    land_nft.total_size = total_size;
+    land_nft.each_size = Some(each_size);
+    land_nft.addr = Some(addr);
+    land_nft.total_lands = total_lands;
+    land_nft.price = price;
+    land_nft.date_updated = _env.block.time;
 
-    LAND_NFTS.save(deps.storage, for_key.as_str(), &new_land)?;
+    if land_nft.plot_count() < land_nft.minted_plot_count() {
+
+        return Err(ContractError::PlotsAlreadyMinted { key : for_key, minted : land_nft.minted_plot_count() });
+    }
+
+    save_land_nft(deps.storage, for_key.as_str(), &land_nft)?;
 
     Ok(Response::new().add_attribute("key", for_key).add_attribute("method", "update_land_nft"))
 }
 
 
+pub fn update_land_nft_description(
+    deps: DepsMut,  _env : Env,  
+    info: MessageInfo,
+    for_key : String , 
+    name : Option<String>, 
+    description : Option<String>) -> Result<Response, ContractError> {
+
+    if !has_role(deps.as_ref(), &info.sender, ROLE_CATALOG_EDITOR) {
+
+        return Err(ContractError::Unauthorized {});
+    }    
+
+    let stored_land = land_nfts().key(for_key.as_str());
+    
+    let stored_land_nft = stored_land.may_load(deps.storage)?
+    .ok_or_else(|| ContractError::LandNftNotFound { key : for_key.clone() })?;
+
+    let mut land_nft = stored_land_nft;
+
+    if name.is_some() {
+        land_nft.name = name;
+    }
+
+    if description.is_some() {
+        land_nft.description = description;
+    }
+
+    land_nft.date_updated = _env.block.time;
+
+    save_land_nft(deps.storage, for_key.as_str(), &land_nft)?;
+
+    Ok(Response::new().add_attribute("key", for_key).add_attribute("method", "update_land_nft_description"))
+}
+
+
 pub fn remove_land_nft ( 
     deps: DepsMut,  
     _env : Env,  
     info: MessageInfo,
     for_key : String ) -> Result<Response, ContractError>{
 
-    if !is_allowed_admin(info.clone()) {
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
 
         return Err(ContractError::Unauthorized {});
     }   
     
     
-    let stored_land = LAND_NFTS.key(for_key.as_str());
+    let stored_land = land_nfts().key(for_key.as_str());
     
-    let _stored_land_nft = stored_land.may_load(deps.storage)?;
-   
-    if _stored_land_nft.is_none() {
+    if stored_land.may_load(deps.storage)?.is_none() {
+
+        return Err(ContractError::LandNftNotFound { key : for_key });
+    }
+
+    // removing a parcel with live plot tokens would leave them orphaned
+    if has_live_token(deps.storage, for_key.as_str())? {
 
-        return Err(ContractError::InvalidLandNft{});
+        return Err(ContractError::LandNftHasLiveToken { key : for_key });
     }
 
-    LAND_NFTS.remove(deps.storage, for_key.as_str());
+    // the reserver's deposit is still held for it
+    if RESERVATIONS.has(deps.storage, for_key.as_str()) {
+
+        return Err(ContractError::LandNftReserved { key : for_key });
+    }
+
+    // and so are the bids of an open auction
+    ensure_no_open_auction(deps.storage, for_key.as_str())?;
+
+    remove_land_nft_entry(deps.storage, for_key.as_str())?;
 
     Ok(Response::new().add_attribute("method", "remove_land_nft").add_attribute("key", for_key))
     
@@ -169,6 +494,48 @@ pub fn remove_land_nft (
 
 
 
+/// The royalty cap can't exceed the whole sale price
+pub fn validate_max_royalty(max_royalty_bps : u16) -> Result<u16, ContractError> {
+
+    if max_royalty_bps > MAX_BASIS_POINTS {
+
+        return Err(ContractError::InvalidRoyalty { royalty : max_royalty_bps });
+    }
+
+    Ok(max_royalty_bps)
+}
+
+/// Checks a new royalty against the ones already on the land nft, 
+/// indexes and creators must be unique and the sum stays within the cap
+fn validate_royalty(land_nft : &LandNft, royalty : &LandNftRoyalty, max_royalty_bps : u16) -> Result<(), ContractError> {
+
+    if royalty.royalty == 0 || royalty.royalty > MAX_BASIS_POINTS {
+
+        return Err(ContractError::InvalidRoyalty { royalty : royalty.royalty });
+    }
+
+    let royalties = land_nft.all_royalties().unwrap_or_default();
+
+    if royalties.iter().any(|r| r.index == royalty.index) {
+
+        return Err(ContractError::DuplicateRoyaltyIndex { index : royalty.index });
+    }
+
+    if royalties.contains(royalty) {
+
+        return Err(ContractError::DuplicateRoyaltyCreator { address : royalty.creator_wallet.to_string() });
+    }
+
+    let total = land_nft.total_royalty_bps() + u32::from(royalty.royalty);
+
+    if total > u32::from(max_royalty_bps) {
+
+        return Err(ContractError::RoyaltyTotalExceeded { total, max : max_royalty_bps });
+    }
+
+    Ok(())
+}
+
 pub fn add_land_nft_royalty(deps: DepsMut,  
     _env : Env, 
     info: MessageInfo,
@@ -176,22 +543,27 @@ pub fn add_land_nft_royalty(deps: DepsMut,
     mut royalty :LandNftRoyalty) -> Result<Response, ContractError> {
    
 
-    if !is_allowed_admin(info.clone()) {
+    if !has_role(deps.as_ref(), &info.sender, ROLE_TREASURER) {
 
         return Err(ContractError::Unauthorized {});
     }    
 
    
-    let stored_land = LAND_NFTS.key(_key.as_str());
+    let stored_land = land_nfts().key(_key.as_str());
     
-    let mut land_nft = stored_land.may_load(deps.storage).expect("Failed to find land nft").unwrap();
+    let mut land_nft = stored_land.may_load(deps.storage)?
+    .ok_or_else(|| ContractError::LandNftNotFound { key : _key.clone() })?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    validate_royalty(&land_nft, &royalty, config.max_royalty_bps)?;
 
     let date_updated = _env.block.time;
     royalty.date_updated = Some(date_updated);
 
     land_nft.add_royalty(royalty, date_updated);
     
-    LAND_NFTS.save(deps.storage, _key.as_str(), &land_nft)?;
+    save_land_nft(deps.storage, _key.as_str(), &land_nft)?;
 
     Ok(Response::new().add_attribute("method", "add_royalty"))
 }
@@ -199,22 +571,30 @@ pub fn add_land_nft_royalty(deps: DepsMut,
 pub fn remove_land_nft_royalty(deps: DepsMut,  _env : Env, 
     info: MessageInfo,
     _key : String ,
-    creator_wallet : Addr ) -> Result<Response, ContractError> {
+    creator_wallet : String ) -> Result<Response, ContractError> {
    
-    if !is_allowed_admin(info.clone()) {
+    if !has_role(deps.as_ref(), &info.sender, ROLE_TREASURER) {
 
         return Err(ContractError::Unauthorized {});
     }    
     
-    let stored_land = LAND_NFTS.key(_key.as_str());
+    let stored_land = land_nfts().key(_key.as_str());
     
-    let mut land_nft = stored_land.may_load(deps.storage).expect("Failed to find land nft").unwrap();
+    let mut land_nft = stored_land.may_load(deps.storage)?
+    .ok_or_else(|| ContractError::LandNftNotFound { key : _key.clone() })?;
+
+    let creator_wallet = validate_address(deps.api, creator_wallet.as_str())?;
+
+    if !land_nft.all_royalties().unwrap_or_default().iter().any(|r| r.creator_wallet == creator_wallet) {
+
+        return Err(ContractError::RoyaltyNotFound { address : creator_wallet.to_string() });
+    }
 
     let date_updated = _env.block.time;
    
     land_nft.remove_royalty(creator_wallet, date_updated);
 
-    LAND_NFTS.save(deps.storage, _key.as_str(), &land_nft)?;
+    save_land_nft(deps.storage, _key.as_str(), &land_nft)?;
 
     Ok(Response::new().add_attribute("method", "remove_royalty"))
 }
@@ -227,22 +607,22 @@ pub fn add_land_nft_media_type(deps: DepsMut,  _env : Env,
     _key : String ,
     mut media_type : LandNftMediaType) -> Result<Response, ContractError> {
    
-    if !is_allowed_admin(info.clone()) {
+    if !has_role(deps.as_ref(), &info.sender, ROLE_CATALOG_EDITOR) {
 
         return Err(ContractError::Unauthorized {});
     }    
     
-    let stored_land = LAND_NFTS.key(_key.as_str());
+    let stored_land = land_nfts().key(_key.as_str());
     
-    let mut land_nft = stored_land.may_load(deps.storage)
-    .expect("Failed to find land nft").expect("x.!.Failed to unwrap!!");
+    let mut land_nft = stored_land.may_load(deps.storage)?
+    .ok_or_else(|| ContractError::LandNftNotFound { key : _key.clone() })?;
 
     let date_updated = _env.block.time;
     media_type.date_updated = Some(date_updated);
 
     land_nft.add_media_type(media_type, date_updated);
     
-    LAND_NFTS.save(deps.storage, _key.as_str(), &land_nft)?;
+    save_land_nft(deps.storage, _key.as_str(), &land_nft)?;
 
     Ok(Response::new().add_attribute("method", "add_media_type"))
 }
@@ -252,248 +632,1546 @@ pub fn remove_land_nft_media_type(deps: DepsMut,  _env : Env,
     _key : String ,
     url : String ) -> Result<Response, ContractError> {
    
-    if !is_allowed_admin(info.clone()) {
+    if !has_role(deps.as_ref(), &info.sender, ROLE_CATALOG_EDITOR) {
 
         return Err(ContractError::Unauthorized {});
     }    
     
-    let stored_land = LAND_NFTS.key(_key.as_str());
+    let stored_land = land_nfts().key(_key.as_str());
     
-    let mut land_nft = stored_land.may_load(deps.storage).expect("Failed to find land nft").unwrap();
+    let mut land_nft = stored_land.may_load(deps.storage)?
+    .ok_or_else(|| ContractError::LandNftNotFound { key : _key.clone() })?;
 
     let date_updated = _env.block.time;
    
     land_nft.remove_media_type(url, date_updated);
     
-    LAND_NFTS.save(deps.storage, _key.as_str(), &land_nft)?;
+    save_land_nft(deps.storage, _key.as_str(), &land_nft)?;
 
     Ok(Response::new().add_attribute("method", "remove_media_type"))
 }
 
 
-// refer to https://docs.opensea.io/docs/metadata-standards
-pub type Metadata = cw721_metadata_onchain::Metadata;
+// refer to https://docs.opensea.io/docs/metadata-standards, 
+// extended with the royalty fields of cw2981
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct Metadata {
+    pub image: Option<String>,
+    pub image_data: Option<String>,
+    pub external_url: Option<String>,
+    pub description: Option<String>,
+    pub name: Option<String>,
+    pub attributes: Option<Vec<cw721_metadata_onchain::Trait>>,
+    pub background_color: Option<String>,
+    pub animation_url: Option<String>,
+    pub youtube_url: Option<String>,
+    /// sum of the land nft's royalties when minted, as the whole percentage cw2981 defines
+    pub royalty_percentage: Option<u64>,
+    pub royalty_payment_address: Option<String>,
+    /// the same sum in basis points, exact where the percentage is rounded down
+    pub royalty_bps: Option<u32>,
+}
 
 pub type Extension = Option<Metadata>;
 
 pub type MyNftMintingContract<'a> = cw721_base::Cw721Contract<'a, Extension, Empty>;
 
 
-pub fn ins_land_nft_for_minting(deps: DepsMut,  _env : Env, 
-    info: MessageInfo, _key : String) -> Result<Response, ContractError> {
-
-    let stored_land = LAND_NFTS.key(_key.as_str());
+fn save_collection_info(storage : &mut dyn Storage, config : &Config) -> Result<(), ContractError> {
 
-    let land_nft = stored_land.may_load(deps.storage).expect("Failed to find land nft").expect(
-        format!("Failed to unwrap, key not found :\"{}\"", _key).as_str());
+    let contract = MyNftMintingContract::default();
 
-    let msg =  cw721_base::InstantiateMsg {
-        name: crate::contract::CONTRACT_NAME.to_string(),
-        symbol: land_nft.symbol,
-        minter: String::from(info.sender.clone()),
-    };
+    contract.contract_info.save(storage, &ContractInfoResponse {
+        name : config.name.clone(),
+        symbol : config.symbol.clone(),
+    })?;
 
-    let res = MyNftMintingContract::default().instantiate(deps, _env.clone(), info.clone(),msg);
+    contract.minter.save(storage, &config.minter)?;
 
-    match res {
+    Ok(())
+}
 
-        Ok(_) => Ok(Response::new().add_attribute("method", "land-nft-instantiated")),
+/// Sets the cw721 collection info and minter, which must only ever happen once
+pub fn init_collection(storage : &mut dyn Storage, config : &Config) -> Result<(), ContractError> {
 
-        Err(e) => Err(ContractError::CustomError{error : e}), 
+    if MyNftMintingContract::default().contract_info.may_load(storage)?.is_some() {
 
+        return Err(ContractError::CollectionAlreadyInitialized {});
     }
+
+    save_collection_info(storage, config)
 }
 
-const DEFAULT_EXTERN_URL_PREFIX : &str = "https://neworld.techchee.com/land-nft";
+pub fn ins_land_nft_for_minting(deps: DepsMut,  _env : Env, 
+    info: MessageInfo) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }    
+
+    let config = CONFIG.load(deps.storage)?;
+
+    init_collection(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("method", "land-nft-instantiated"))
+}
 
+#[allow(clippy::too_many_arguments)]
 pub fn mint_land_nft(mut deps: DepsMut,  _env : Env, 
-    info: MessageInfo, _key : String, _extern_url_prefix : Option <String>) -> Result<Response, ContractError> {
+    info: MessageInfo, _key : String, plot : Option<u16>, _extern_url_prefix : Option <String>, 
+    proof : Option<Vec<String>>, allocation : Option<u32>) -> Result<Response, ContractError> {
+
+    let phase = check_mint_phase(deps.as_ref(), &_env, &info.sender, 1, proof, allocation)?;
 
-    let deps_branch = deps.branch();
     let new_owner = info.clone().sender;
     
-    let stored_land = LAND_NFTS.key(_key.as_str());
+    let stored_land = land_nfts().key(_key.as_str());
+
+    let mut land_nft = stored_land.may_load(deps.storage)?
+    .ok_or_else(|| ContractError::LandNftNotFound { key : _key.clone() })?;
 
-    let land_nft = stored_land.may_load(deps_branch.storage).expect("Failed to find land nft").expect(
-        format!("Failed to unwrap, key not found :\"{}\"", _key).as_str());
+    let reservation = claim_reservation(deps.storage, &_env, &mut land_nft, _key.as_str(), &new_owner)?;
 
     if land_nft.status.is_some() {
 
-        return Err(ContractError::CustomErrorMesg{ message : 
-        format!("Land NFT {} already minted or transferred", _key)});
+        return Err(ContractError::AlreadyMinted { key : _key });
     }
 
-    let mut land_nft2 = land_nft.clone();
+    ensure_no_open_auction(deps.storage, _key.as_str())?;
 
-    let mut  ext_url_prefix = _extern_url_prefix ;
+    let config = CONFIG.load(deps.storage)?;
 
-    if ext_url_prefix.is_none(){
-        ext_url_prefix = Some(DEFAULT_EXTERN_URL_PREFIX.to_string());
-    }
+    let (price_denom, price) = payment_price(deps.storage, &config, &land_nft, phase.as_ref(), 
+        _env.block.time, &info.funds)?;
 
-    let key = land_nft.clone().key.expect("Failed to unwrap land nft's key");
-    let ext_url : Option<String> = Some(format!("{}/{}", ext_url_prefix.unwrap(), key));
+    let deposit = match &reservation {
 
+        Some(r) if r.deposit.denom == price_denom => r.deposit.amount,
 
-    let mut _image_url : Option<String> = land_nft.default_media_type_url(crate::state::MEDIA_TYPE_IMAGE);
-    let mut _video_url : Option<String> = land_nft.default_media_type_url(crate::state::MEDIA_TYPE_VIDEO);
-    let mut _anim_url : Option<String> = land_nft.default_media_type_url(crate::state::MEDIA_TYPE_ANIMATION);
-    let attribs = land_nft.to_metadata_attributes();
-    
-    let ext = Some(Metadata {
-        description: land_nft.description,
-        name: land_nft.name ,
-        image : _image_url, 
-        youtube_url : _video_url,
-        animation_url : _anim_url, 
-        external_url : ext_url.clone() ,
-        attributes : Some(attribs), 
-        ..Metadata::default()
-    });
+        // the price moved to another denom since, so the deposit goes back to the reserver
+        Some(r) => {
 
-    
-    let msg = cw721_base::msg::MintMsg {
-        token_id: key.clone() ,
-        owner: new_owner.to_string(),
-        token_uri: ext_url,
-        extension: ext ,
+            credit_balance(deps.storage, &r.reserver, r.deposit.denom.as_str(), r.deposit.amount)?;
+            Uint128::zero()
+        },
+
+        None => Uint128::zero(),
     };
 
-    let mint_msg = cw721_base::msg::ExecuteMsg::Mint(msg.clone());
+    // the deposit is already held by the contract, anything above the price is refunded
+    let overpaid = check_payment(&info.funds, price.saturating_sub(deposit), price_denom.as_str())? 
+        + deposit.saturating_sub(price);
 
-    let res = MyNftMintingContract::default().execute(deps_branch, _env.clone(), info, mint_msg);
+    if reservation.is_some() {
 
+        RESERVATIONS.remove(deps.storage, _key.as_str());
+    }
 
-    match res {
+    let token_id = mint_plot(deps.branch(), &_env, &config, land_nft.clone(), plot, 
+        &new_owner, _extern_url_prefix)?;
 
-        Ok(_) =>  {
+    credit_treasuries(deps.storage, &config.treasuries, price, price_denom.as_str())?;
 
-            let date_updated = _env.block.time;
+    record_phase_mints(deps.storage, phase.as_ref(), &new_owner, 1)?;
 
-            land_nft2.date_updated = date_updated;
-            land_nft2.status = Some(crate::state::LAND_NFT_STATUS_MINTED);
-            land_nft2.owner = new_owner;
+    let mut resp = Response::new().add_attribute("action", "credited-all-treasuries");
 
-            LAND_NFTS.save(deps.storage, key.as_str(), &land_nft2)?;
+    if !overpaid.is_zero() {
 
-            let res = pay_treasuries(land_nft.price,land_nft.price_denom, None );
+        resp = resp.add_message(payout_msg(&new_owner, Coin { denom : price_denom, amount : overpaid })?);
+    }
+    
+    Ok(resp.add_attribute("method", "land-nft-minted").add_attribute("token_id", token_id))
+}
 
-            if res.is_err() {
+/// Mints the next available plot of each parcel in one go, a parcel key may be 
+/// repeated to buy several of its plots, any failure reverts the whole batch
+pub fn batch_mint_land_nft(mut deps: DepsMut,  _env : Env, 
+    info: MessageInfo, keys : Vec<String>, _extern_url_prefix : Option <String>, 
+    proof : Option<Vec<String>>, allocation : Option<u32>) -> Result<Response, ContractError> {
 
-                return Err(ContractError::CustomErrorMesg{message : 
-                    "some error while paying treasuries".to_string()});
-            }
+    if keys.is_empty() {
 
-            let resp = res.expect("Failed to unwrap pay treasuries' response");
-            
-            Ok(resp.add_attribute("method", "land-nft-minted"))
-        },
+        return Err(ContractError::EmptyBatch {});
+    }
 
-        Err(e) => Err(ContractError::CustomErrorMesg{message : e.to_string()}), 
+    let phase = check_mint_phase(deps.as_ref(), &_env, &info.sender, keys.len() as u32, proof, allocation)?;
 
-    }
+    let config = CONFIG.load(deps.storage)?;
 
-}
+    let mut required : Vec<Coin> = vec![];
 
-pub fn ins_and_mint_nft (mut deps: DepsMut,  _env : Env, 
-    info: MessageInfo, for_key : String, _extern_url_prefix : Option <String>)-> Result<Response, ContractError> {
+    for key in keys.iter() {
 
-    let deps_branch = deps.branch();
+        let land_nft = land_nfts().may_load(deps.storage, key.as_str())?
+        .ok_or_else(|| ContractError::LandNftNotFound { key : key.clone() })?;
 
-    let res = ins_land_nft_for_minting(deps_branch, _env.clone(), info.clone(), for_key.clone());
+        ensure_no_open_auction(deps.storage, key.as_str())?;
 
-    match res {
+        // reserved land nfts are minted one at a time by their reserver
+        if land_nft.status == Some(LAND_NFT_STATUS_RESERVED) {
 
-        Ok(_) =>{
+            return Err(ContractError::LandNftReserved { key : key.clone() });
+        }
 
-            mint_land_nft(deps, _env, info, for_key, _extern_url_prefix)
-        },
+        let wanted = keys.iter().filter(|k| *k == key).count();
 
-        Err(e) =>{
+        if land_nft.status.is_some() || wanted > land_nft.remaining_plot_count() as usize {
 
-            return Err(ContractError::CustomErrorMesg{message : e.to_string()});
+            return Err(ContractError::NotEnoughPlots { key : key.clone() });
         }
-    }
-}
-
 
-pub const TREASURIES : [Treasury; 2] = [
-    Treasury {wallet_address : "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0", percentage : 95},
-    Treasury {wallet_address : "terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", percentage : 5} ];
+        let price_denom = land_nft.price_denom.clone().unwrap_or_else(|| config.price_denom.clone());
+        let price = mint_price(deps.storage, &land_nft, phase.as_ref(), _env.block.time)?;
 
+        match required.iter_mut().find(|c| c.denom == price_denom) {
 
-pub const DEFAULT_PRICE_DENOM : &str = "uusd";
+            Some(c) => c.amount += price,
 
-#[allow(dead_code)]
-fn convert(x: u64) -> f64 {
-    let result = x as f64;
-    if result as u64 != x {
-        return 0.0;
+            None => required.push(Coin { denom : price_denom, amount : price }),
+        }
     }
-    return result;
-}
 
-pub fn pay_treasuries (total_amount : u64, _denom : Option<String>, debug : Option<bool>) -> 
-Result<Response, ContractError>{
+    let refunds = check_payments(&info.funds, &required)?;
 
-    let mut error : Option<ContractError> = None ;
+    let mut token_ids : Vec<String> = vec![];
 
-    TREASURIES.iter().for_each( |t| {
+    for key in keys.iter() {
 
-        let perc : f64 = convert(t.percentage as u64) / 100.00;
-        let amount = (convert(total_amount) * perc) as u64;
+        let land_nft = land_nfts().load(deps.storage, key.as_str())?;
 
-        if debug.is_some() && debug.unwrap_or(false) {
+        let price_denom = land_nft.price_denom.clone().unwrap_or_else(|| config.price_denom.clone());
+        let price = mint_price(deps.storage, &land_nft, phase.as_ref(), _env.block.time)?;
 
-            println!("Paid.amount:{}:{}:{}", t.wallet_address, perc,  amount );
+        token_ids.push(mint_plot(deps.branch(), &_env, &config, land_nft, None, 
+            &info.sender, _extern_url_prefix.clone())?);
 
-        }
-      
-        let res =  pay_treasury(t.wallet_address, amount, _denom.clone());
+        credit_treasuries(deps.storage, &config.treasuries, price, price_denom.as_str())?;
+    }
 
-        match res {
+    record_phase_mints(deps.storage, phase.as_ref(), &info.sender, keys.len() as u32)?;
 
-            Ok(_) => error = None  ,
+    let mut resp = Response::new().add_attribute("action", "credited-all-treasuries");
 
-            Err(e) => error = Some(ContractError::CustomErrorMesg{ message : e.to_string()} ),
+    for refund in refunds {
 
+        resp = resp.add_message(payout_msg(&info.sender, refund)?);
+    }
+
+    Ok(resp.add_attribute("method", "land-nfts-batch-minted")
+    .add_attribute("token_ids", token_ids.join(",")))
+}
+
+fn plot_token_exists(storage : &dyn Storage, token_id : &str) -> StdResult<bool> {
+
+    Ok(MyNftMintingContract::default().tokens.may_load(storage, token_id)?.is_some())
+}
+
+/// Checks if any plot of the parcel still has a cw721 token
+fn has_live_token(storage : &dyn Storage, key : &str) -> StdResult<bool> {
+
+    let land_nft = match land_nfts().may_load(storage, key)? {
+
+        Some(land_nft) => land_nft,
+
+        None => return Ok(false),
+    };
+
+    for plot in 1..=land_nft.plot_count() {
+
+        if plot_token_exists(storage, LandNft::token_id(key, plot).as_str())? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Finds the first unminted plot, starting after the number of plots minted so far
+fn next_available_plot(storage : &dyn Storage, land_nft : &LandNft, key : &str) -> StdResult<Option<u16>> {
+
+    let count = land_nft.plot_count();
+    let start = land_nft.minted_plot_count() % count;
+
+    for i in 0..count {
+
+        let plot = (start + i) % count + 1;
+
+        if !land_nft.is_plot_burned(plot) && 
+        !plot_token_exists(storage, LandNft::token_id(key, plot).as_str())? {
+            return Ok(Some(plot));
         }
+    }
+
+    Ok(None)
+}
+
+/// Mints a single plot of the parcel to the owner and records it on the parcel,
+/// the parcel is only marked minted once all of its plots are sold
+pub fn mint_plot(mut deps : DepsMut, _env : &Env, config : &Config, 
+    mut land_nft : LandNft, plot : Option<u16>, owner : &Addr, 
+    _extern_url_prefix : Option <String>) -> Result<String, ContractError> {
+
+    let key = land_nft.key.clone().ok_or_else(|| ContractError::LandNftNotFound { key : String::new() })?;
+
+    let plot = match plot {
+
+        Some(p) => p,
+
+        None => next_available_plot(deps.storage, &land_nft, key.as_str())?.unwrap_or(0),
+    };
+
+    let token_id = LandNft::token_id(key.as_str(), plot);
 
+    if plot == 0 || plot > land_nft.plot_count() || land_nft.is_plot_burned(plot) ||
+    plot_token_exists(deps.storage, token_id.as_str())? {
+
+        return Err(ContractError::PlotNotAvailable { token_id });
+    }
+
+    let ext_url_prefix = _extern_url_prefix.unwrap_or_else(|| config.external_url_prefix.clone());
+
+    let ext_url : Option<String> = Some(format!("{}/{}/{}", ext_url_prefix, key, plot));
+
+    let mut _image_url : Option<String> = land_nft.default_media_type_url(crate::state::MEDIA_TYPE_IMAGE);
+    let mut _video_url : Option<String> = land_nft.default_media_type_url(crate::state::MEDIA_TYPE_VIDEO);
+    let mut _anim_url : Option<String> = land_nft.default_media_type_url(crate::state::MEDIA_TYPE_ANIMATION);
+    let attribs = land_nft.to_plot_metadata_attributes(plot);
+    
+    let ext = Some(Metadata {
+        description: land_nft.description.clone(),
+        name: land_nft.name.clone().map(|n| format!("{} - Plot {}", n, plot)),
+        image : _image_url, 
+        youtube_url : _video_url,
+        animation_url : _anim_url, 
+        external_url : ext_url.clone() ,
+        attributes : Some(attribs), 
+        royalty_percentage : land_nft.royalty_payment_address().map(|_| u64::from(land_nft.total_royalty_bps() / 100)),
+        royalty_payment_address : land_nft.royalty_payment_address().map(|a| a.to_string()),
+        royalty_bps : land_nft.royalty_payment_address().map(|_| land_nft.total_royalty_bps()),
+        ..Metadata::default()
     });
 
-    if error.is_none() {
+    let msg = cw721_base::msg::MintMsg {
+        token_id: token_id.clone() ,
+        owner: owner.to_string(),
+        token_uri: ext_url,
+        extension: ext ,
+    };
+
+    let mint_msg = cw721_base::msg::ExecuteMsg::Mint(msg);
+
+    // the collection only accepts mints from its configured minter
+    let minter_info = MessageInfo { sender : config.minter.clone(), funds : vec![] };
+
+    MyNftMintingContract::default().execute(deps.branch(), _env.clone(), minter_info, mint_msg)?;
+
+    land_nft.minted_plots = Some(land_nft.minted_plot_count() + 1);
+    land_nft.date_updated = _env.block.time;
 
-        Ok(Response::new().add_attribute("action", "paid-all-teasuries"))
+    if land_nft.remaining_plot_count() == 0 {
+
+        land_nft.status = Some(crate::state::LAND_NFT_STATUS_MINTED);
+        land_nft.owner = owner.clone();
     }
-    else {
 
-        let err = error.unwrap_or(ContractError::CustomErrorMesg{message: String::from(
-            "Some Error When Paying All Treasuries!")});
+    save_land_nft(deps.storage, key.as_str(), &land_nft)?;
+
+    Ok(token_id)
+}
+
+/// Validates a bech32 address, reporting the address itself when it's invalid
+pub fn validate_address(api : &dyn Api, address : &str) -> Result<Addr, ContractError> {
+
+    api.addr_validate(address).map_err(|_| ContractError::InvalidAddress { address : address.to_string() })
+}
+
+/// Delegates the standard cw721 messages to the embedded collection,
+/// keeping the parcel's owner and status in sync when a plot changes hands
+pub fn execute_cw721(mut deps: DepsMut,  _env : Env, 
+    info: MessageInfo, msg : cw721_base::msg::ExecuteMsg<Extension>) -> Result<Response, ContractError> {
+
+    let transfer = match &msg {
+
+        cw721_base::msg::ExecuteMsg::TransferNft { recipient, token_id } => Some((recipient.clone(), token_id.clone())),
+
+        cw721_base::msg::ExecuteMsg::SendNft { contract, token_id, .. } => Some((contract.clone(), token_id.clone())),
+
+        // minting only goes through mint_land_nft
+        cw721_base::msg::ExecuteMsg::Mint(_) => return Err(ContractError::Unauthorized {}),
+
+        _ => None,
+    };
+
+    let res = MyNftMintingContract::default().execute(deps.branch(), _env.clone(), info, msg)?;
+
+    if let Some((new_owner, token_id)) = transfer {
+
+        let new_owner = validate_address(deps.api, new_owner.as_str())?;
+
+        sync_land_nft_owner(deps.storage, &_env, token_id.as_str(), &new_owner)?;
+    }
+
+    Ok(res)
+}
+
+/// Burns a plot token, only its owner or an admin can burn it,
+/// the plot is recorded on the parcel so it is never minted again
+pub fn burn_land_nft(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, token_id : String) -> Result<Response, ContractError> {
+
+    let contract = MyNftMintingContract::default();
+
+    let token = contract.tokens.may_load(deps.storage, token_id.as_str())?
+    .ok_or_else(|| ContractError::TokenNotFound { token_id : token_id.clone() })?;
+
+    if token.owner != info.sender && !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    contract.tokens.remove(deps.storage, token_id.as_str())?;
+
+    // an admin may burn a token escrowed for sale or for its installments
+    listings().remove(deps.storage, token_id.as_str())?;
+    INSTALLMENT_PLANS.remove(deps.storage, token_id.as_str());
+
+    let count = contract.token_count(deps.storage)?;
+    contract.token_count.save(deps.storage, &count.saturating_sub(1))?;
+
+    if let Some((key, plot)) = LandNft::parse_token_id(token_id.as_str()) {
+
+        if let Some(mut land_nft) = land_nfts().may_load(deps.storage, key.as_str())? {
+
+            land_nft.burn_plot(plot, _env.block.time);
+
+            save_land_nft(deps.storage, key.as_str(), &land_nft)?;
+        }
+    }
+
+    Ok(Response::new()
+    .add_attribute("method", "burn_land_nft")
+    .add_attribute("token_id", token_id))
+}
+
+/// Puts the next available plot of the land nft up for auction, 
+/// fixed price minting of the land nft is paused until it's settled
+#[allow(clippy::too_many_arguments)]
+pub fn start_auction(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, for_key : String, reserve_price : Uint128, denom : String, 
+    start : Timestamp, end : Timestamp, min_increment : Uint128) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if denom.is_empty() || end <= start || end <= _env.block.time {
+
+        return Err(ContractError::InvalidAuction {});
+    }
+
+    let land_nft = land_nfts().may_load(deps.storage, for_key.as_str())?
+    .ok_or_else(|| ContractError::LandNftNotFound { key : for_key.clone() })?;
+
+    if land_nft.status == Some(LAND_NFT_STATUS_RESERVED) {
+
+        return Err(ContractError::LandNftReserved { key : for_key });
+    }
+
+    if land_nft.status.is_some() || land_nft.remaining_plot_count() == 0 {
+
+        return Err(ContractError::AlreadyMinted { key : for_key });
+    }
+
+    ensure_no_open_auction(deps.storage, for_key.as_str())?;
+
+    let auction = Auction {
+        for_key : for_key.clone(),
+        reserve_price,
+        denom,
+        start,
+        end,
+        min_increment,
+        bids : vec![],
+        settled : false,
+    };
+
+    AUCTIONS.save(deps.storage, for_key.as_str(), &auction)?;
+
+    Ok(Response::new()
+    .add_attribute("method", "start_auction")
+    .add_attribute("key", for_key))
+}
+
+/// Places a bid with the attached funds, the outbid amount is credited 
+/// back to the previous bidder's pending balance
+pub fn place_bid(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, for_key : String) -> Result<Response, ContractError> {
+
+    let mut auction = AUCTIONS.may_load(deps.storage, for_key.as_str())?
+    .ok_or_else(|| ContractError::AuctionNotFound { key : for_key.clone() })?;
+
+    if !auction.is_open(_env.block.time) {
+
+        return Err(ContractError::AuctionNotOpen { key : for_key });
+    }
+
+    if info.funds.iter().any(|c| c.denom != auction.denom) {
+
+        return Err(ContractError::WrongDenom { expected : auction.denom });
+    }
+
+    let amount = info.funds.iter().fold(Uint128::zero(), |acc, c| acc + c.amount);
+
+    let minimum = auction.minimum_bid();
+
+    if amount < minimum {
+
+        return Err(ContractError::BidTooLow { minimum, denom : auction.denom });
+    }
+
+    if let Some(outbid) = auction.highest_bid() {
+
+        credit_balance(deps.storage, &outbid.bidder, auction.denom.as_str(), outbid.amount)?;
+    }
+
+    auction.bids.push(Bid { bidder : info.sender.clone(), amount, date_placed : _env.block.time });
+
+    // late bids extend the auction so others get a chance to respond
+    let extended_end = _env.block.time.plus_seconds(AUCTION_TIME_EXTENSION);
+
+    if extended_end > auction.end {
+        auction.end = extended_end;
+    }
+
+    AUCTIONS.save(deps.storage, for_key.as_str(), &auction)?;
+
+    Ok(Response::new()
+    .add_attribute("method", "place_bid")
+    .add_attribute("key", for_key)
+    .add_attribute("amount", format!("{}{}", amount, auction.denom)))
+}
+
+/// Closes an ended auction, the plot is minted to the highest bidder 
+/// and the winning bid is split among the treasuries
+pub fn settle_auction(mut deps: DepsMut,  _env : Env, 
+    _info: MessageInfo, for_key : String) -> Result<Response, ContractError> {
+
+    let mut auction = AUCTIONS.may_load(deps.storage, for_key.as_str())?
+    .ok_or_else(|| ContractError::AuctionNotFound { key : for_key.clone() })?;
+
+    if auction.settled || _env.block.time < auction.end {
+
+        return Err(ContractError::AuctionNotEnded { key : for_key });
+    }
+
+    auction.settled = true;
+
+    AUCTIONS.save(deps.storage, for_key.as_str(), &auction)?;
+
+    let mut resp = Response::new()
+    .add_attribute("method", "settle_auction")
+    .add_attribute("key", for_key.clone());
+
+    if let Some(winner) = auction.highest_bid() {
+
+        let config = CONFIG.load(deps.storage)?;
+
+        let minted = match land_nfts().may_load(deps.storage, for_key.as_str())? {
+
+            Some(land_nft) => match mint_plot(deps.branch(), &_env, &config, land_nft, None, &winner.bidder, None) {
+
+                Ok(token_id) => Some(token_id),
+
+                Err(ContractError::PlotNotAvailable { .. }) => None,
+
+                Err(e) => return Err(e),
+            },
+
+            None => None,
+        };
+
+        match minted {
+
+            Some(token_id) => {
+
+                credit_treasuries(deps.storage, &config.treasuries, winner.amount, auction.denom.as_str())?;
+
+                resp = resp.add_attribute("token_id", token_id)
+                .add_attribute("winner", winner.bidder.to_string());
+            },
+
+            // nothing left to mint, so the winning bid is refunded and the auction dropped
+            None => {
+
+                credit_balance(deps.storage, &winner.bidder, auction.denom.as_str(), winner.amount)?;
+
+                AUCTIONS.remove(deps.storage, for_key.as_str());
+
+                resp = resp.add_attribute("refunded", winner.bidder.to_string());
+            },
+        }
+    }
+
+    Ok(resp)
+}
+
+pub fn set_dutch_auction(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, for_key : Option<String>, auction : DutchAuction) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !is_valid_dutch_auction_mode(auction.mode) || auction.decay_interval == 0 || auction.decay_step.is_zero() 
+    || auction.floor > auction.start_price {
+
+        return Err(ContractError::InvalidDutchAuction {});
+    }
+
+    match for_key {
+
+        Some(key) => {
+
+            if land_nfts().may_load(deps.storage, key.as_str())?.is_none() {
+
+                return Err(ContractError::LandNftNotFound { key });
+            }
+
+            DUTCH_AUCTIONS.save(deps.storage, key.as_str(), &auction)?;
+        },
+
+        None => DEFAULT_DUTCH_AUCTION.save(deps.storage, &Some(auction))?,
+    }
+
+    Ok(Response::new().add_attribute("method", "set_dutch_auction"))
+}
+
+pub fn clear_dutch_auction(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, for_key : Option<String>) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match for_key {
+
+        Some(key) => DUTCH_AUCTIONS.remove(deps.storage, key.as_str()),
 
-        Err(err) 
+        None => DEFAULT_DUTCH_AUCTION.save(deps.storage, &None)?,
     }
+
+    Ok(Response::new().add_attribute("method", "clear_dutch_auction"))
+}
+
+pub fn set_sale_phases(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, mut phases : Vec<SalePhase>) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    phases.sort_by_key(|p| p.start);
+
+    for (i, p) in phases.iter().enumerate() {
+
+        let overlaps = i > 0 && phases[i - 1].end > p.start;
+        let duplicate = phases.iter().filter(|o| o.name == p.name).count() > 1;
+
+        // a merkle root only makes sense on an allowlisted phase
+        let bad_root = p.merkle_root.as_ref().map(|r| !p.has_allowlist || merkle::decode_hash(r).is_none()).unwrap_or(false);
+
+        if p.name.is_empty() || p.end <= p.start || overlaps || duplicate || bad_root {
+
+            return Err(ContractError::InvalidSalePhases {});
+        }
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    config.sale_phases = phases;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("method", "set_sale_phases"))
 }
 
-fn pay_treasury (wallet_address : &str, amount : u64, _denom : Option <String>)
--> Result<Response, ContractError>{
+pub fn update_allowlist(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, phase : String, addresses : Vec<String>, allowed : bool) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
 
-    if amount == 0 {
-        return Err(ContractError::CustomErrorMesg {message : "Invalid Amount".to_string()});
+    if !config.sale_phases.iter().any(|p| p.name == phase) {
+
+        return Err(ContractError::SalePhaseNotFound { name : phase });
+    }
+
+    for address in addresses.iter() {
+
+        let addr = validate_address(deps.api, address.as_str())?;
+
+        if allowed {
+            ALLOWLIST.save(deps.storage, (phase.as_str(), &addr), &true)?;
+        }
+        else {
+            ALLOWLIST.remove(deps.storage, (phase.as_str(), &addr));
+        }
     }
 
-    let mut denom = String::from(DEFAULT_PRICE_DENOM);
+    Ok(Response::new()
+    .add_attribute("method", if allowed { "add_to_allowlist" } else { "remove_from_allowlist" })
+    .add_attribute("phase", phase)
+    .add_attribute("count", addresses.len().to_string()))
+}
+
+/// Checks the sender can mint the number of plots in the active sale phase,
+/// minters aren't bound by the phases and get none
+fn check_mint_phase(deps : Deps, _env : &Env, sender : &Addr, count : u32, 
+    proof : Option<Vec<String>>, allocation : Option<u32>) -> Result<Option<SalePhase>, ContractError> {
+
+    if has_role(deps, sender, ROLE_MINTER) {
 
-    if _denom != None {
-        denom = _denom.unwrap_or( String::from( DEFAULT_PRICE_DENOM) );
+        return Ok(None);
     }
 
-    let real_amt = u128::try_from(amount);
+    let config = CONFIG.load(deps.storage)?;
+
+    let phase = match config.active_sale_phase(_env.block.time) {
+
+        Some(p) => p.clone(),
 
-    let bank_mesg = BankMsg::Send {
-        to_address: String::from(wallet_address),
-        amount: coins(real_amt.expect("Invalid Amount")  , denom)
+        // without phases minting stays with the minters
+        None if config.sale_phases.is_empty() => return Err(ContractError::Unauthorized {}),
+
+        None => return Err(ContractError::NoActiveSalePhase {}),
     };
 
-    Ok(Response::new().add_attribute("action", "approve").add_message(bank_mesg))
+    let mut mint_cap = phase.mint_cap;
+
+    match (&phase.merkle_root, proof) {
+
+        (Some(_), None) => return Err(ContractError::NotAllowlisted { phase : phase.name }),
+
+        (Some(root), Some(proof)) => {
+
+            // the allocation is part of the leaf so it can't be inflated by the sender
+            let allocation = allocation.ok_or_else(|| ContractError::InvalidMerkleProof { phase : phase.name.clone() })?;
+
+            if !merkle::verify(root.as_str(), merkle::leaf_hash(sender.as_str(), allocation), &proof) {
+
+                return Err(ContractError::InvalidMerkleProof { phase : phase.name });
+            }
+
+            mint_cap = Some(mint_cap.map_or(allocation, |cap| cap.min(allocation)));
+        },
+
+        _ if phase.has_allowlist && !ALLOWLIST.has(deps.storage, (phase.name.as_str(), sender)) => {
+
+            return Err(ContractError::NotAllowlisted { phase : phase.name });
+        },
+
+        _ => {},
+    }
+
+    if let Some(cap) = mint_cap {
+
+        let minted = PHASE_MINTS.may_load(deps.storage, (phase.name.as_str(), sender))?.unwrap_or(0);
+
+        if minted + count > cap {
+
+            return Err(ContractError::MintCapReached { phase : phase.name, cap });
+        }
+    }
+
+    Ok(Some(phase))
+}
+
+fn record_phase_mints(storage : &mut dyn Storage, phase : Option<&SalePhase>, sender : &Addr, count : u32) -> StdResult<()> {
 
-}
\ No newline at end of file
+    if let Some(phase) = phase {
+
+        PHASE_MINTS.update(storage, (phase.name.as_str(), sender), |m| -> StdResult<u32> {
+            Ok(m.unwrap_or(0) + count)
+        })?;
+    }
+
+    Ok(())
+}
+
+/// The phase's price if it overrides it, or else the live price of the land nft
+fn mint_price(storage : &dyn Storage, land_nft : &LandNft, phase : Option<&SalePhase>, now : Timestamp) -> StdResult<Uint128> {
+
+    match phase.and_then(|p| p.price) {
+
+        Some(price) => Ok(price),
+
+        None => land_nft_price(storage, land_nft, now),
+    }
+}
+
+/// Sets or clears the deposit and duration every reservation is made on
+pub fn set_reservation_terms(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, terms : Option<ReservationTerms>) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(t) = &terms {
+
+        if t.deposit_bps == 0 || t.deposit_bps > MAX_BASIS_POINTS || t.duration == 0 {
+
+            return Err(ContractError::InvalidReservationTerms {});
+        }
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    config.reservation_terms = terms;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("method", "set_reservation_terms"))
+}
+
+/// Holds an unminted single plot land nft for the sender against a deposit, the sender 
+/// must be allowed to mint it now and the sale phase rules apply again when they mint
+pub fn reserve_land_nft(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, for_key : String, proof : Option<Vec<String>>, allocation : Option<u32>) -> Result<Response, ContractError> {
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let terms = config.reservation_terms.clone().ok_or(ContractError::ReservationsDisabled {})?;
+
+    let mut land_nft = land_nfts().may_load(deps.storage, for_key.as_str())?
+    .ok_or_else(|| ContractError::LandNftNotFound { key : for_key.clone() })?;
+
+    if land_nft.status == Some(LAND_NFT_STATUS_RESERVED) {
+
+        return Err(ContractError::LandNftReserved { key : for_key });
+    }
+
+    if land_nft.status.is_some() || land_nft.remaining_plot_count() == 0 {
+
+        return Err(ContractError::AlreadyMinted { key : for_key });
+    }
+
+    // the reservation holds the whole land nft, so it can only be for a single plot
+    if land_nft.plot_count() > 1 {
+
+        return Err(ContractError::ReservationMultiplePlots { key : for_key });
+    }
+
+    ensure_no_open_auction(deps.storage, for_key.as_str())?;
+
+    let phase = check_mint_phase(deps.as_ref(), &_env, &info.sender, 1, proof, allocation)?;
+
+    let (denom, price) = payment_price(deps.storage, &config, &land_nft, phase.as_ref(), _env.block.time, &info.funds)?;
+
+    let deposit = price.multiply_ratio(terms.deposit_bps, MAX_BASIS_POINTS);
+
+    let overpaid = check_payment(&info.funds, deposit, denom.as_str())?;
+
+    let reservation = Reservation {
+        for_key : for_key.clone(),
+        reserver : info.sender.clone(),
+        deposit : Coin { denom : denom.clone(), amount : deposit },
+        expires : _env.block.time.plus_seconds(terms.duration),
+        forfeit : terms.forfeit,
+        date_reserved : _env.block.time,
+    };
+
+    RESERVATIONS.save(deps.storage, for_key.as_str(), &reservation)?;
+
+    land_nft.status = Some(LAND_NFT_STATUS_RESERVED);
+    land_nft.date_updated = _env.block.time;
+
+    save_land_nft(deps.storage, for_key.as_str(), &land_nft)?;
+
+    let mut resp = Response::new();
+
+    if !overpaid.is_zero() {
+
+        resp = resp.add_message(payout_msg(&info.sender, Coin { denom, amount : overpaid })?);
+    }
+
+    Ok(resp.add_attribute("method", "reserve_land_nft")
+    .add_attribute("key", for_key)
+    .add_attribute("expires", reservation.expires.seconds().to_string()))
+}
+
+/// Frees an expired reservation, anyone can call it. The deposit is 
+/// forfeited to the treasuries or credited back to the reserver
+pub fn release_reservation(deps: DepsMut,  _env : Env, 
+    _info: MessageInfo, for_key : String) -> Result<Response, ContractError> {
+
+    let reservation = RESERVATIONS.may_load(deps.storage, for_key.as_str())?
+    .ok_or_else(|| ContractError::ReservationNotFound { key : for_key.clone() })?;
+
+    if !reservation.is_expired(_env.block.time) {
+
+        return Err(ContractError::ReservationNotExpired { key : for_key });
+    }
+
+    RESERVATIONS.remove(deps.storage, for_key.as_str());
+
+    if let Some(mut land_nft) = land_nfts().may_load(deps.storage, for_key.as_str())? {
+
+        land_nft.status = None;
+        land_nft.date_updated = _env.block.time;
+
+        save_land_nft(deps.storage, for_key.as_str(), &land_nft)?;
+    }
+
+    let deposit = reservation.deposit;
+
+    if reservation.forfeit {
+
+        let config = CONFIG.load(deps.storage)?;
+
+        credit_treasuries(deps.storage, &config.treasuries, deposit.amount, deposit.denom.as_str())?;
+    }
+    else {
+
+        credit_balance(deps.storage, &reservation.reserver, deposit.denom.as_str(), deposit.amount)?;
+    }
+
+    Ok(Response::new()
+    .add_attribute("method", "release_reservation")
+    .add_attribute("key", for_key)
+    .add_attribute("forfeited", reservation.forfeit.to_string()))
+}
+
+/// Lets the reserver mint a reserved land nft before the reservation expires,
+/// the reservation itself is removed once the payment is checked
+fn claim_reservation(storage : &dyn Storage, _env : &Env, land_nft : &mut LandNft, 
+    key : &str, sender : &Addr) -> Result<Option<Reservation>, ContractError> {
+
+    if land_nft.status != Some(LAND_NFT_STATUS_RESERVED) {
+
+        return Ok(None);
+    }
+
+    let reservation = RESERVATIONS.load(storage, key)?;
+
+    if reservation.reserver != *sender || reservation.is_expired(_env.block.time) {
+
+        return Err(ContractError::LandNftReserved { key : key.to_string() });
+    }
+
+    land_nft.status = None;
+
+    Ok(Some(reservation))
+}
+
+pub fn set_payment_plan(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, for_key : String, plan : Option<PaymentPlan>) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if land_nfts().may_load(deps.storage, for_key.as_str())?.is_none() {
+
+        return Err(ContractError::LandNftNotFound { key : for_key });
+    }
+
+    match plan {
+
+        Some(p) if !p.is_valid() => return Err(ContractError::InvalidPaymentPlan {}),
+
+        Some(p) => PAYMENT_PLANS.save(deps.storage, for_key.as_str(), &p)?,
+
+        None => PAYMENT_PLANS.remove(deps.storage, for_key.as_str()),
+    }
+
+    Ok(Response::new().add_attribute("method", "set_payment_plan").add_attribute("key", for_key))
+}
+
+/// Mints the next available plot of the land nft to the contract against the first installment,
+/// the plan's amounts make up the price so any sale phase price is ignored
+pub fn start_payment_plan(mut deps: DepsMut,  _env : Env, 
+    info: MessageInfo, for_key : String, proof : Option<Vec<String>>, allocation : Option<u32>) -> Result<Response, ContractError> {
+
+    let phase = check_mint_phase(deps.as_ref(), &_env, &info.sender, 1, proof, allocation)?;
+
+    let plan = PAYMENT_PLANS.may_load(deps.storage, for_key.as_str())?
+    .ok_or_else(|| ContractError::PaymentPlanNotFound { key : for_key.clone() })?;
+
+    let land_nft = land_nfts().may_load(deps.storage, for_key.as_str())?
+    .ok_or_else(|| ContractError::LandNftNotFound { key : for_key.clone() })?;
+
+    if land_nft.status == Some(LAND_NFT_STATUS_RESERVED) {
+
+        return Err(ContractError::LandNftReserved { key : for_key });
+    }
+
+    if land_nft.status.is_some() {
+
+        return Err(ContractError::AlreadyMinted { key : for_key });
+    }
+
+    ensure_no_open_auction(deps.storage, for_key.as_str())?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let denom = land_nft.price_denom.clone().unwrap_or_else(|| config.price_denom.clone());
+
+    let first = plan.amounts[0];
+
+    let overpaid = check_payment(&info.funds, first, denom.as_str())?;
+
+    let token_id = mint_plot(deps.branch(), &_env, &config, land_nft, None, &_env.contract.address, None)?;
+
+    credit_treasuries(deps.storage, &config.treasuries, first, denom.as_str())?;
+
+    record_phase_mints(deps.storage, phase.as_ref(), &info.sender, 1)?;
+
+    let installments = InstallmentPlan {
+        token_id : token_id.clone(),
+        buyer : info.sender.clone(),
+        denom : denom.clone(),
+        plan,
+        paid : 1,
+        amount_paid : first,
+        date_started : _env.block.time,
+    };
+
+    let released = save_installment_plan(deps.branch(), &_env, &installments)?;
+
+    let mut resp = Response::new();
+
+    if !overpaid.is_zero() {
+
+        resp = resp.add_message(payout_msg(&info.sender, Coin { denom, amount : overpaid })?);
+    }
+
+    Ok(resp.add_attribute("method", "start_payment_plan")
+    .add_attribute("token_id", token_id)
+    .add_attribute("released", released.to_string()))
+}
+
+/// Pays the buyer's next installment, the token is released to them with the last one
+pub fn pay_installment(mut deps: DepsMut,  _env : Env, 
+    info: MessageInfo, token_id : String) -> Result<Response, ContractError> {
+
+    let mut installments = INSTALLMENT_PLANS.may_load(deps.storage, token_id.as_str())?
+    .ok_or_else(|| ContractError::InstallmentPlanNotFound { token_id : token_id.clone() })?;
+
+    if installments.buyer != info.sender {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let amount = installments.next_amount()
+    .ok_or_else(|| ContractError::InstallmentPlanNotFound { token_id : token_id.clone() })?;
+
+    let overpaid = check_payment(&info.funds, amount, installments.denom.as_str())?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    credit_treasuries(deps.storage, &config.treasuries, amount, installments.denom.as_str())?;
+
+    installments.paid += 1;
+    installments.amount_paid += amount;
+
+    let released = save_installment_plan(deps.branch(), &_env, &installments)?;
+
+    let mut resp = Response::new();
+
+    if !overpaid.is_zero() {
+
+        resp = resp.add_message(payout_msg(&info.sender, Coin { denom : installments.denom, amount : overpaid })?);
+    }
+
+    Ok(resp.add_attribute("method", "pay_installment")
+    .add_attribute("token_id", token_id)
+    .add_attribute("released", released.to_string()))
+}
+
+/// Takes back a plot whose installment is overdue past the grace period, the token 
+/// is burned and the plot goes back on sale, the installments paid are kept
+pub fn repossess_land_nft(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, token_id : String) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let installments = INSTALLMENT_PLANS.may_load(deps.storage, token_id.as_str())?
+    .ok_or_else(|| ContractError::InstallmentPlanNotFound { token_id : token_id.clone() })?;
+
+    if !installments.is_overdue(_env.block.time) {
+
+        return Err(ContractError::InstallmentNotOverdue { token_id });
+    }
+
+    INSTALLMENT_PLANS.remove(deps.storage, token_id.as_str());
+
+    free_plot(deps.storage, &_env, token_id.as_str())?;
+
+    Ok(Response::new()
+    .add_attribute("method", "repossess_land_nft")
+    .add_attribute("token_id", token_id)
+    .add_attribute("buyer", installments.buyer))
+}
+
+/// Keeps the plan until its last installment is paid, then releases 
+/// the escrowed token to the buyer, returns whether it was released
+fn save_installment_plan(mut deps : DepsMut, _env : &Env, installments : &InstallmentPlan) -> Result<bool, ContractError> {
+
+    let token_id = installments.token_id.as_str();
+
+    if installments.next_amount().is_some() {
+
+        INSTALLMENT_PLANS.save(deps.storage, token_id, installments)?;
+
+        return Ok(false);
+    }
+
+    INSTALLMENT_PLANS.remove(deps.storage, token_id);
+
+    release_escrowed_token(deps.branch(), _env, token_id, &installments.buyer)?;
+
+    sync_land_nft_owner(deps.storage, _env, token_id, &installments.buyer)?;
+
+    Ok(true)
+}
+
+/// Removes a plot's token from the collection, unlike burning 
+/// the plot can be minted again
+fn free_plot(storage : &mut dyn Storage, _env : &Env, token_id : &str) -> Result<(), ContractError> {
+
+    let contract = MyNftMintingContract::default();
+
+    contract.tokens.remove(storage, token_id)?;
+
+    let count = contract.token_count(storage)?;
+    contract.token_count.save(storage, &count.saturating_sub(1))?;
+
+    if let Some((key, _)) = LandNft::parse_token_id(token_id) {
+
+        if let Some(mut land_nft) = land_nfts().may_load(storage, key.as_str())? {
+
+            land_nft.minted_plots = Some(land_nft.minted_plot_count().saturating_sub(1));
+
+            // the plot goes back on sale, a reservation on the remaining plots still holds
+            if land_nft.status != Some(LAND_NFT_STATUS_RESERVED) && land_nft.remaining_plot_count() > 0 {
+                land_nft.status = None;
+            }
+
+            land_nft.date_updated = _env.block.time;
+
+            save_land_nft(storage, key.as_str(), &land_nft)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn set_cw20_price(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, for_key : String, contract : String, price : Option<Uint128>) -> Result<Response, ContractError> {
+
+    if !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let contract = validate_address(deps.api, contract.as_str())?;
+
+    if land_nfts().may_load(deps.storage, for_key.as_str())?.is_none() {
+
+        return Err(ContractError::LandNftNotFound { key : for_key });
+    }
+
+    match price {
+
+        Some(p) if p.is_zero() => return Err(ContractError::InvalidPrice {}),
+
+        Some(p) => CW20_PRICES.save(deps.storage, (for_key.as_str(), &contract), &p)?,
+
+        None => CW20_PRICES.remove(deps.storage, (for_key.as_str(), &contract)),
+    }
+
+    Ok(Response::new().add_attribute("method", "set_cw20_price")
+    .add_attribute("key", for_key)
+    .add_attribute("contract", contract))
+}
+
+/// Handles cw20 tokens sent with a hook message, the sending cw20 contract is the 
+/// caller and the tokens stand in for the funds of the original sender
+pub fn receive_cw20(deps: DepsMut,  _env : Env, 
+    info: MessageInfo, wrapper : Cw20ReceiveMsg) -> Result<Response, ContractError> {
+
+    let msg : Cw20HookMsg = from_binary(&wrapper.msg)?;
+
+    let info = MessageInfo {
+        sender : validate_address(deps.api, wrapper.sender.as_str())?,
+        funds : vec![Coin { denom : cw20_denom(&info.sender), amount : wrapper.amount }],
+    };
+
+    match msg {
+
+        Cw20HookMsg::MintLandNft { for_key, plot, external_url_prefix, proof, allocation } =>
+        mint_land_nft(deps, _env, info, for_key, plot, external_url_prefix, proof, allocation),
+
+        Cw20HookMsg::Buy { token_id } => buy(deps, _env, info, token_id),
+
+        Cw20HookMsg::PlaceBid { for_key } => place_bid(deps, _env, info, for_key),
+
+        Cw20HookMsg::ReserveLandNft { for_key, proof, allocation } => 
+        reserve_land_nft(deps, _env, info, for_key, proof, allocation),
+    }
+}
+
+/// The denom and price the land nft is paid in, a payment in cw20 tokens 
+/// is priced by the land nft's accepted cw20 tokens instead
+fn payment_price(storage : &dyn Storage, config : &Config, land_nft : &LandNft, phase : Option<&SalePhase>, 
+    now : Timestamp, funds : &[Coin]) -> Result<(String, Uint128), ContractError> {
+
+    if let Some(contract) = funds.iter().find_map(|c| cw20_contract(c.denom.as_str())) {
+
+        let contract = Addr::unchecked(contract);
+        let key = land_nft.key.clone().unwrap_or_default();
+
+        let price = CW20_PRICES.may_load(storage, (key.as_str(), &contract))?
+        .ok_or_else(|| ContractError::Cw20NotAccepted { contract : contract.to_string() })?;
+
+        return Ok((cw20_denom(&contract), price));
+    }
+
+    let denom = land_nft.price_denom.clone().unwrap_or_else(|| config.price_denom.clone());
+
+    Ok((denom, mint_price(storage, land_nft, phase, now)?))
+}
+
+/// Fixed price minting of a land nft waits for its auction to be settled
+fn ensure_no_open_auction(storage : &dyn Storage, key : &str) -> Result<(), ContractError> {
+
+    if AUCTIONS.may_load(storage, key)?.map(|a| !a.settled).unwrap_or(false) {
+
+        return Err(ContractError::AuctionActive { key : key.to_string() });
+    }
+
+    Ok(())
+}
+
+/// Lists a plot token for resale, the token is escrowed by the contract 
+/// until it's bought or the listing is cancelled
+pub fn list_for_sale(mut deps: DepsMut,  _env : Env, 
+    info: MessageInfo, token_id : String, price : Uint128, denom : String, 
+    expires : Option<Expiration>) -> Result<Response, ContractError> {
+
+    if price.is_zero() || denom.is_empty() {
+
+        return Err(ContractError::InvalidPrice {});
+    }
+
+    if let Some(expires) = expires {
+
+        if expires.is_expired(&_env.block) {
+
+            return Err(ContractError::ListingExpired { token_id });
+        }
+    }
+
+    let contract = MyNftMintingContract::default();
+
+    let token = contract.tokens.may_load(deps.storage, token_id.as_str())?
+    .ok_or_else(|| ContractError::TokenNotFound { token_id : token_id.clone() })?;
+
+    // the cw721 transfer checks the sender is the owner or an approved spender
+    contract.execute(deps.branch(), _env.clone(), info, cw721_base::msg::ExecuteMsg::TransferNft { 
+        recipient : _env.contract.address.to_string(), token_id : token_id.clone() })?;
+
+    let listing = Listing {
+        token_id : token_id.clone(),
+        seller : token.owner,
+        price,
+        denom,
+        expires,
+        date_listed : _env.block.time,
+    };
+
+    listings().save(deps.storage, token_id.as_str(), &listing)?;
+
+    Ok(Response::new()
+    .add_attribute("method", "list_for_sale")
+    .add_attribute("token_id", token_id)
+    .add_attribute("price", format!("{}{}", listing.price, listing.denom)))
+}
+
+/// Returns an escrowed token to its seller, only the seller or an admin can cancel
+pub fn cancel_listing(mut deps: DepsMut,  _env : Env, 
+    info: MessageInfo, token_id : String) -> Result<Response, ContractError> {
+
+    let listing = listings().may_load(deps.storage, token_id.as_str())?
+    .ok_or_else(|| ContractError::ListingNotFound { token_id : token_id.clone() })?;
+
+    if listing.seller != info.sender && !is_allowed_admin(deps.as_ref(), info.clone()) {
+
+        return Err(ContractError::Unauthorized {});
+    }
+
+    release_escrowed_token(deps.branch(), &_env, token_id.as_str(), &listing.seller)?;
+
+    listings().remove(deps.storage, token_id.as_str())?;
+
+    Ok(Response::new()
+    .add_attribute("method", "cancel_listing")
+    .add_attribute("token_id", token_id))
+}
+
+/// Buys a listed token, the royalties of its land nft are credited to 
+/// the creators' pending balances and the rest is sent to the seller
+pub fn buy(mut deps: DepsMut,  _env : Env, 
+    info: MessageInfo, token_id : String) -> Result<Response, ContractError> {
+
+    let listing = listings().may_load(deps.storage, token_id.as_str())?
+    .ok_or_else(|| ContractError::ListingNotFound { token_id : token_id.clone() })?;
+
+    if listing.expires.map(|e| e.is_expired(&_env.block)).unwrap_or(false) {
+
+        return Err(ContractError::ListingExpired { token_id });
+    }
+
+    let overpaid = check_payment(&info.funds, listing.price, listing.denom.as_str())?;
+
+    let mut royalties = Uint128::zero();
+
+    if let Some((key, _)) = LandNft::parse_token_id(token_id.as_str()) {
+
+        if let Some(land_nft) = land_nfts().may_load(deps.storage, key.as_str())? {
+
+            for (royalty, amount) in land_nft.royalty_shares(listing.price) {
+
+                credit_balance(deps.storage, &royalty.creator_wallet, listing.denom.as_str(), amount)?;
+                royalties += amount;
+            }
+        }
+    }
+
+    release_escrowed_token(deps.branch(), &_env, token_id.as_str(), &info.sender)?;
+
+    listings().remove(deps.storage, token_id.as_str())?;
+
+    sync_land_nft_owner(deps.storage, &_env, token_id.as_str(), &info.sender)?;
+
+    let mut resp = Response::new();
+
+    let proceeds = listing.price.checked_sub(royalties).map_err(StdError::from)?;
+
+    if !proceeds.is_zero() {
+
+        resp = resp.add_message(payout_msg(&listing.seller, Coin { denom : listing.denom.clone(), amount : proceeds })?);
+    }
+
+    if !overpaid.is_zero() {
+
+        resp = resp.add_message(payout_msg(&info.sender, Coin { denom : listing.denom, amount : overpaid })?);
+    }
+
+    Ok(resp
+    .add_attribute("method", "buy")
+    .add_attribute("token_id", token_id)
+    .add_attribute("royalties", royalties.to_string()))
+}
+
+/// Transfers a token escrowed by the contract to the recipient
+fn release_escrowed_token(deps: DepsMut, _env : &Env, token_id : &str, recipient : &Addr) -> Result<(), ContractError> {
+
+    let contract_info = MessageInfo { sender : _env.contract.address.clone(), funds : vec![] };
+
+    MyNftMintingContract::default().execute(deps, _env.clone(), contract_info, cw721_base::msg::ExecuteMsg::TransferNft { 
+        recipient : recipient.to_string(), token_id : token_id.to_string() })?;
+
+    Ok(())
+}
+
+/// Marks a fully minted parcel as transferred, and moves its ownership 
+/// once the new owner holds every one of its plots
+fn sync_land_nft_owner(storage : &mut dyn Storage, _env : &Env, token_id : &str, new_owner : &Addr) -> Result<(), ContractError> {
+
+    let key = match LandNft::parse_token_id(token_id) {
+
+        Some((key, _)) => key,
+
+        None => return Ok(()),
+    };
+
+    let mut land_nft = match land_nfts().may_load(storage, key.as_str())? {
+
+        Some(land_nft) => land_nft,
+
+        None => return Ok(()),
+    };
+
+    if land_nft.remaining_plot_count() > 0 {
+        return Ok(());
+    }
+
+    let contract = MyNftMintingContract::default();
+
+    let mut owns_all = true;
+
+    for plot in (1..=land_nft.plot_count()).filter(|p| !land_nft.is_plot_burned(*p)) {
+
+        let token = contract.tokens.may_load(storage, LandNft::token_id(key.as_str(), plot).as_str())?;
+
+        if token.map(|t| t.owner != *new_owner).unwrap_or(true) {
+            owns_all = false;
+            break;
+        }
+    }
+
+    if owns_all {
+        land_nft.owner = new_owner.clone();
+    }
+
+    land_nft.status = Some(LAND_NFT_STATUS_TRANSFERRED);
+    land_nft.date_updated = _env.block.time;
+
+    save_land_nft(storage, key.as_str(), &land_nft)?;
+
+    Ok(())
+}
+
+/// Checks the attached funds cover the price in the expected denom,
+/// returns the overpaid amount to be refunded
+pub fn check_payment(funds : &[Coin], price : Uint128, denom : &str) -> Result<Uint128, ContractError> {
+
+    let refunds = check_payments(funds, &[Coin { denom : denom.to_string(), amount : price }])?;
+
+    Ok(refunds.iter().fold(Uint128::zero(), |acc, c| acc + c.amount))
+}
+
+/// Checks the attached funds cover every required amount in its denom,
+/// returns the overpaid amounts to be refunded
+pub fn check_payments(funds : &[Coin], required : &[Coin]) -> Result<Vec<Coin>, ContractError> {
+
+    if funds.iter().any(|c| !c.amount.is_zero() && !required.iter().any(|r| r.denom == c.denom)) {
+
+        let expected = required.iter().map(|r| r.denom.clone()).collect::<Vec<String>>().join(",");
+
+        return Err(ContractError::WrongDenom { expected });
+    }
+
+    let mut refunds : Vec<Coin> = vec![];
+
+    for r in required.iter() {
+
+        let paid = funds.iter().filter(|c| c.denom == r.denom)
+        .fold(Uint128::zero(), |acc, c| acc + c.amount);
+
+        if paid < r.amount {
+
+            return Err(ContractError::InsufficientFunds { required : r.amount, denom : r.denom.clone() });
+        }
+
+        if paid > r.amount {
+            refunds.push(Coin { denom : r.denom.clone(), amount : paid - r.amount });
+        }
+    }
+
+    Ok(refunds)
+}
+
+/// Splits the amount across the treasuries by their basis points, 
+/// the rounding remainder goes to the primary treasury
+pub fn treasury_shares (treasuries : &[Treasury], total_amount : Uint128) -> 
+Result<Vec<(Addr, Uint128)>, ContractError>{
+
+    let mut shares : Vec<Uint128> = treasuries.iter().map(|t| 
+        total_amount.multiply_ratio(t.basis_points as u128, TREASURY_TOTAL_BPS as u128)
+    ).collect();
+
+    let paid = shares.iter().fold(Uint128::zero(), |acc, s| acc + *s);
+
+    if let Some(pos) = treasuries.iter().position(|t| t.is_primary) {
+        shares[pos] += total_amount.checked_sub(paid).map_err(StdError::from)?;
+    }
+
+    Ok(treasuries.iter().map(|t| t.wallet_address.clone()).zip(shares)
+    .filter(|(_, amount)| !amount.is_zero()).collect())
+}
+
+pub fn credit_balance (storage : &mut dyn Storage, beneficiary : &Addr, denom : &str, amount : Uint128) -> 
+Result<(), ContractError> {
+
+    if amount.is_zero() {
+        return Ok(());
+    }
+
+    PENDING_BALANCES.update(storage, (beneficiary, denom), |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default() + amount)
+    })?;
+
+    Ok(())
+}
+
+/// Credits each treasury's share to its pending balance, to be withdrawn later
+pub fn credit_treasuries (storage : &mut dyn Storage, treasuries : &[Treasury], total_amount : Uint128, denom : &str) -> 
+Result<(), ContractError>{
+
+    for (wallet_address, amount) in treasury_shares(treasuries, total_amount)? {
+
+        credit_balance(storage, &wallet_address, denom, amount)?;
+    }
+
+    Ok(())
+}
+
+/// Sends the coin to the recipient, as a cw20 transfer when it's in a cw20 denom
+pub fn payout_msg(recipient : &Addr, coin : Coin) -> StdResult<CosmosMsg> {
+
+    let msg : CosmosMsg = match cw20_contract(coin.denom.as_str()) {
+
+        Some(contract) => WasmMsg::Execute {
+            contract_addr : contract.to_string(),
+            msg : to_binary(&Cw20ExecuteMsg::Transfer { recipient : recipient.to_string(), amount : coin.amount })?,
+            funds : vec![],
+        }.into(),
+
+        None => BankMsg::Send { to_address : recipient.to_string(), amount : vec![coin] }.into(),
+    };
+
+    Ok(msg)
+}
+
+pub fn withdraw(deps: DepsMut, _env : Env, 
+    info: MessageInfo, denom : String, amount : Uint128) -> Result<Response, ContractError> {
+
+    let balance = PENDING_BALANCES.may_load(deps.storage, (&info.sender, denom.as_str()))?.unwrap_or_default();
+
+    if amount.is_zero() || amount > balance {
+
+        return Err(ContractError::InsufficientPendingBalance { available : balance, denom });
+    }
+
+    let remaining = balance - amount;
+
+    if remaining.is_zero() {
+        PENDING_BALANCES.remove(deps.storage, (&info.sender, denom.as_str()));
+    }
+    else {
+        PENDING_BALANCES.save(deps.storage, (&info.sender, denom.as_str()), &remaining)?;
+    }
+
+    let payout = payout_msg(&info.sender, Coin { denom, amount })?;
+
+    Ok(Response::new().add_message(payout).add_attribute("method", "withdraw")
+    .add_attribute("amount", amount.to_string()))
+}
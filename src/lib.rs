@@ -6,5 +6,6 @@ pub mod ins;
 pub mod resp;
 mod tests;
 pub mod get;
+pub mod merkle;
 
 pub use crate::error::ContractError;
@@ -0,0 +1,117 @@
+//! Merkle tree of a sale phase's allowlist. Each leaf is the sha256 of 
+//! "address,allocation" and each pair is sorted before it's hashed, 
+//! so a proof is just the list of sibling hashes in hex.
+
+use sha2::{Digest, Sha256};
+
+pub type Hash = [u8; 32];
+
+pub fn leaf_hash(address : &str, allocation : u32) -> Hash {
+
+    Sha256::digest(format!("{},{}", address, allocation).as_bytes()).into()
+}
+
+fn hash_pair(a : &Hash, b : &Hash) -> Hash {
+
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+
+    let mut hasher = Sha256::new();
+    hasher.update(first);
+    hasher.update(second);
+    hasher.finalize().into()
+}
+
+/// Checks the proof leads from the leaf up to the hex encoded root,
+/// a malformed root or proof never verifies
+pub fn verify(root : &str, leaf : Hash, proof : &[String]) -> bool {
+
+    let mut computed = leaf;
+
+    for sibling in proof.iter() {
+
+        match decode_hash(sibling) {
+
+            Some(s) => computed = hash_pair(&computed, &s),
+
+            None => return false,
+        }
+    }
+
+    decode_hash(root).map(|r| r == computed).unwrap_or(false)
+}
+
+pub fn decode_hash(value : &str) -> Option<Hash> {
+
+    let bytes = hex::decode(value).ok()?;
+
+    if bytes.len() != 32 {
+        return None;
+    }
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&bytes);
+
+    Some(hash)
+}
+
+/// All layers of the tree from the leaves up to the root, 
+/// an odd node out is carried up as is
+fn layers(leaves : &[Hash]) -> Vec<Vec<Hash>> {
+
+    let mut layers = vec![leaves.to_vec()];
+
+    while layers.last().map(|l| l.len() > 1).unwrap_or(false) {
+
+        let next = layers.last().unwrap_or(&vec![]).chunks(2).map(|pair| match pair {
+
+            [a, b] => hash_pair(a, b),
+
+            _ => pair[0],
+        }).collect::<Vec<Hash>>();
+
+        layers.push(next);
+    }
+
+    layers
+}
+
+pub fn root(leaves : &[Hash]) -> Option<Hash> {
+
+    layers(leaves).last()?.first().copied()
+}
+
+pub fn proof(leaves : &[Hash], index : usize) -> Vec<Hash> {
+
+    let mut proof = vec![];
+    let mut index = index;
+
+    for layer in layers(leaves).iter() {
+
+        let sibling = index ^ 1;
+
+        if sibling < layer.len() {
+            proof.push(layer[sibling]);
+        }
+
+        index /= 2;
+    }
+
+    proof
+}
+
+/// Parses the "address,allocation" lines of an allowlist, 
+/// blank lines and lines starting with # are skipped
+pub fn parse_csv(csv : &str) -> Result<Vec<(String, u32)>, String> {
+
+    csv.lines()
+    .map(|l| l.trim())
+    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+    .map(|l| {
+
+        let (address, allocation) = l.split_once(',').ok_or(format!("Missing allocation : \"{}\"", l))?;
+
+        let allocation = allocation.trim().parse::<u32>().map_err(|_| format!("Invalid allocation : \"{}\"", l))?;
+
+        Ok((address.trim().to_string(), allocation))
+    }).collect()
+}
@@ -1,8 +1,26 @@
+use cosmwasm_std::{Binary, Timestamp, Uint128};
+use cw721::Expiration;
+use cw20::Cw20ReceiveMsg;
+use crate::state::{SalePhase, ReservationTerms, PaymentPlan};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
-    pub count: i32,
+    pub name: String,
+    pub symbol: Option<String>,
+    pub price_denom: Option<String>,
+    pub external_url_prefix: Option<String>,
+    pub minter: Option<String>,
+    pub admins: Vec<String>,
+    pub treasuries: Vec<TreasuryMsg>,
+    pub max_royalty_bps: Option<u16>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TreasuryMsg {
+    pub wallet_address : String,
+    pub basis_points : u16,
+    pub is_primary : bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -34,6 +52,12 @@ pub enum ExecuteMsg {
         price_denom : Option<String>
     },
 
+    UpdateLandNftDescription {
+        for_key : String, 
+        name : Option<String>,
+        description : Option<String>,
+    },
+
     RemoveLandNft { 
         for_key : String, 
     },
@@ -49,6 +73,7 @@ pub enum ExecuteMsg {
         for_key : String, 
         creator_wallet : String,
         index  : u8,
+        /// basis points of the sale price
         royalty : u16,
     },
 
@@ -64,25 +89,257 @@ pub enum ExecuteMsg {
     },
 
 
-    InstantiateMinting {
+    InstantiateMinting {},
+
+    MintLandNft {
         for_key : String, 
+        plot : Option<u16>,
+        external_url_prefix : Option<String>,
+        /// hex proof of the sender's allocation in the merkle allowlist of the phase
+        proof : Option<Vec<String>>,
+        allocation : Option<u32>,
+    },
+
+    BatchMintLandNft {
+        keys : Vec<String>,
+        external_url_prefix : Option<String>,
+        proof : Option<Vec<String>>,
+        allocation : Option<u32>,
+    },
+
+    TransferNft { 
+        recipient : String, 
+        token_id : String,
+    },
+
+    SendNft {
+        contract : String,
+        token_id : String,
+        msg : Binary,
+    },
+
+    Approve {
+        spender : String,
+        token_id : String,
+        expires : Option<Expiration>,
+    },
+
+    Revoke { 
+        spender : String, 
+        token_id : String,
+    },
+
+    ApproveAll {
+        operator : String,
+        expires : Option<Expiration>,
+    },
+
+    RevokeAll { 
+        operator : String,
+    },
+
+    ListForSale {
+        token_id : String,
+        price : Uint128,
+        denom : String,
+        expires : Option<Expiration>,
+    },
+
+    CancelListing {
+        token_id : String,
+    },
+
+    Buy {
+        token_id : String,
+    },
+
+    StartAuction {
+        for_key : String,
+        reserve_price : Uint128,
+        denom : String,
+        start : Timestamp,
+        end : Timestamp,
+        min_increment : Uint128,
+    },
+
+    PlaceBid {
+        for_key : String,
+    },
+
+    SettleAuction {
+        for_key : String,
+    },
+
+    /// sets the dutch auction of a land nft, or the default one 
+    /// for all unminted land nfts when no key is given
+    SetDutchAuction {
+        for_key : Option<String>,
+        mode : u8,
+        start_price : Uint128,
+        floor : Uint128,
+        start : Timestamp,
+        decay_interval : u64,
+        decay_step : Uint128,
+    },
+
+    ClearDutchAuction {
+        for_key : Option<String>,
+    },
+
+    SetSalePhases {
+        phases : Vec<SalePhase>,
+    },
+
+    AddToAllowlist {
+        phase : String,
+        addresses : Vec<String>,
+    },
+
+    RemoveFromAllowlist {
+        phase : String,
+        addresses : Vec<String>,
+    },
+
+    /// sets the deposit and duration of reservations, 
+    /// no terms disables new reservations
+    SetReservationTerms {
+        terms : Option<ReservationTerms>,
+    },
+
+    /// only single plot land nfts can be reserved
+    ReserveLandNft {
+        for_key : String,
+        proof : Option<Vec<String>>,
+        allocation : Option<u32>,
+    },
+
+    ReleaseReservation {
+        for_key : String,
+    },
+
+    /// attaches a payment plan to a land nft, no plan removes it
+    SetPaymentPlan {
+        for_key : String,
+        plan : Option<PaymentPlan>,
+    },
+
+    /// mints the next available plot into escrow against the first installment
+    StartPaymentPlan {
+        for_key : String,
+        proof : Option<Vec<String>>,
+        allocation : Option<u32>,
+    },
+
+    PayInstallment {
+        token_id : String,
+    },
+
+    RepossessLandNft {
+        token_id : String,
+    },
+
+    /// accepts a cw20 token for the land nft at the given price, no price stops accepting it
+    SetCw20Price {
+        for_key : String,
+        contract : String,
+        price : Option<Uint128>,
+    },
+
+    /// cw20 tokens sent with a Cw20HookMsg
+    Receive(Cw20ReceiveMsg),
+
+    BurnLandNft {
+        token_id : String,
+    },
+
+    AddAdmin {
+        address : String,
     },
 
+    RemoveAdmin {
+        address : String,
+    },
+
+    UpdateConfig {
+        name : Option<String>,
+        symbol : Option<String>,
+        price_denom : Option<String>,
+        external_url_prefix : Option<String>,
+        minter : Option<String>,
+        max_royalty_bps : Option<u16>,
+    },
+
+    SetTreasuries {
+        treasuries : Vec<TreasuryMsg>,
+    },
+
+    Withdraw {
+        denom : String,
+        amount : Uint128,
+    },
+
+    GrantRole {
+        address : String,
+        role : u8,
+    },
+
+    RevokeRole {
+        address : String,
+        role : u8,
+    },
+
+}
+
+/// The cw2981 royalty queries, see https://github.com/CosmWasm/cw-nfts/tree/main/contracts/cw2981-royalties
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw2981QueryMsg {
+
+    RoyaltyInfo {
+        token_id : String,
+        sale_price : Uint128,
+    },
+
+    CheckRoyalties {},
+}
+
+/// The actions that can be paid with cw20 tokens, the tokens sent 
+/// take the place of the native funds of the matching ExecuteMsg
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+
     MintLandNft {
         for_key : String, 
+        plot : Option<u16>,
         external_url_prefix : Option<String>,
+        proof : Option<Vec<String>>,
+        allocation : Option<u32>,
     },
 
-    InsAndMintLandNft {
-        for_key : String, 
-        external_url_prefix : Option<String>,      
+    Buy {
+        token_id : String,
     },
 
+    PlaceBid {
+        for_key : String,
+    },
+
+    ReserveLandNft {
+        for_key : String,
+        proof : Option<Vec<String>>,
+        allocation : Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
+
+    Extension {
+        msg : Cw2981QueryMsg,
+    },
+
   
     GetAllLandNfts { 
         start_after : Option<String>,
@@ -95,10 +352,24 @@ pub enum QueryMsg {
         limit: Option<u32>
     },
 
+    GetAllLandNftsByOwner {
+        owner : String, 
+        start_after : Option<String>,
+        limit: Option<u32>
+    },
+
+    GetAllLandNftsByDenom {
+        price_denom : String, 
+        start_after : Option<String>,
+        limit: Option<u32>
+    },
+
     GetCountOfLandNftsBy {
         status : Option<u8>, 
     },
 
+    CatalogStats {},
+
     GetLandNftByIndex {
         status : Option<u8>, 
         index : u32, 
@@ -114,10 +385,59 @@ pub enum QueryMsg {
         for_key : String,      
     },
 
+    RoyaltyInfo {
+        token_id : String,
+        sale_price : Uint128,
+    },
+
+    Listing {
+        token_id : String,
+    },
+
+    Auction {
+        for_key : String,
+    },
+
+    Reservation {
+        for_key : String,
+    },
+
+    PaymentPlan {
+        for_key : String,
+    },
+
+    Cw20Prices {
+        for_key : String,
+    },
+
+    InstallmentPlan {
+        token_id : String,
+    },
+
+    CurrentPrice {
+        for_key : String,
+    },
+
+    MintEligibility {
+        address : String,
+    },
+
+    Listings {
+        denom : Option<String>,
+        min_price : Option<Uint128>,
+        max_price : Option<Uint128>,
+        start_after : Option<String>,
+        limit : Option<u32>,
+    },
+
     GetLandNft {
         key : String, 
     },
 
+    RemainingPlots {
+        for_key : String,
+    },
+
     NumOfMintedTokens {},
 
     MintedTokensByOwner {
@@ -139,4 +459,18 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
 
+    ListAdmins {},
+
+    Config {},
+
+    Treasuries {},
+
+    PendingBalance {
+        address : String,
+    },
+
+    RolesOf {
+        address : String,
+    },
+
 }
@@ -1,4 +1,6 @@
-use crate::state::{LandNftMediaType, LandNftRoyalty, LandNft};
+use crate::state::{LandNftMediaType, LandNftRoyalty, LandNft, Config, Treasury, Listing, Auction, Reservation, PaymentPlan, InstallmentPlan};
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
+use cw20::Cw20CoinVerified;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -40,3 +42,136 @@ pub struct OptionalLandNftResponse {
 pub struct LandNftCountResponse {
     pub count : usize,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminsResponse {
+    pub admins : Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RolesResponse {
+    pub address : Addr,
+    pub roles : Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub config : Config,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TreasuriesResponse {
+    pub treasuries : Vec<Treasury>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingBalanceResponse {
+    pub address : Addr,
+    pub balances : Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RemainingPlotsResponse {
+    pub for_key : String,
+    pub total_plots : u16,
+    pub minted_plots : u16,
+    pub remaining_plots : u16,
+}
+
+/// Count of land nfts with a status, 0 stands for not minted yet
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatusCount {
+    pub status : u8,
+    pub count : u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CatalogStatsResponse {
+    pub total : u64,
+    pub by_status : Vec<StatusCount>,
+    pub total_area : u64,
+    /// value of the plots still listed for sale, per denom
+    pub listed_values : Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltyPayout {
+    pub creator_wallet : Addr,
+    pub royalty : u16,
+    pub amount : Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltyInfoResponse {
+    pub token_id : String,
+    pub sale_price : Uint128,
+    pub payouts : Vec<RoyaltyPayout>,
+    pub total_amount : Uint128,
+}
+
+/// cw2981 royalty owed on a sale, paid to a single address
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltiesInfoResponse {
+    pub address : String,
+    pub royalty_amount : Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CheckRoyaltiesResponse {
+    pub royalty_payments : bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListingResponse {
+    pub listing : Option<Listing>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListingsResponse {
+    pub listings : Vec<Listing>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuctionResponse {
+    pub auction : Option<Auction>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReservationResponse {
+    pub reservation : Option<Reservation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Cw20PricesResponse {
+    pub for_key : String,
+    pub prices : Vec<Cw20CoinVerified>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PaymentPlanResponse {
+    pub plan : Option<PaymentPlan>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstallmentPlanResponse {
+    pub plan : Option<InstallmentPlan>,
+    /// when the next installment is due, none once paid in full
+    pub next_due : Option<Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CurrentPriceResponse {
+    pub for_key : String,
+    pub price : Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintEligibilityResponse {
+    pub address : Addr,
+    pub eligible : bool,
+    /// the active sale phase, none for minters and outside of the phases
+    pub phase : Option<String>,
+    pub minted : u32,
+    pub remaining : Option<u32>,
+    pub price : Option<Uint128>,
+}
@@ -1,14 +1,28 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use cosmwasm_std::{Addr, Timestamp};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint128, Storage, StdResult};
+use cw721::Expiration;
+use cw_storage_plus::{Item, Map, IndexedMap, MultiIndex, IndexList, Index, U8Key};
 use std::char::from_u32;
 
+/// Total of all treasury splits, in basis points
+pub const TREASURY_TOTAL_BPS : u16 = 10000;
+
+/// Basis points of a whole sale price
+pub const MAX_BASIS_POINTS : u16 = 10000;
+
+/// Default cap on the sum of royalties of a land nft, in basis points
+pub const DEFAULT_MAX_ROYALTY_BPS : u16 = 1000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Treasury  {
 
-    pub wallet_address : &'static str ,
+    pub wallet_address : Addr,
+
+    pub basis_points : u16, 
 
-    pub percentage : u8, 
+    /// the primary treasury receives the rounding remainder
+    pub is_primary : bool,
 }
 
 
@@ -45,6 +59,7 @@ pub struct LandNftRoyalty {
 
     pub index : u8, 
 
+    /// share of the sale price in basis points
     pub royalty : u16, 
 
     pub date_updated : Option<Timestamp>, 
@@ -80,6 +95,14 @@ pub struct IndexCounter {
     index : u32,
 }
 
+impl Default for IndexCounter {
+
+    fn default() -> Self {
+
+        IndexCounter::new()
+    }
+}
+
 impl IndexCounter {
 
     pub fn new() -> IndexCounter{
@@ -100,18 +123,519 @@ impl IndexCounter {
 
 pub const DEFAULT_PRICE_DENOM : &str = "uusd";
 
+pub const ADMINS : Item<Vec<Addr>> = Item::new("admins");
+
+pub const ROLE_SUPER_ADMIN : u8 = 1;
+
+pub const ROLE_CATALOG_EDITOR : u8 = 2;
+
+pub const ROLE_MINTER : u8 = 3;
+
+pub const ROLE_TREASURER : u8 = 4;
+
+pub fn is_valid_role(role : u8) -> bool {
+
+    (ROLE_SUPER_ADMIN..=ROLE_TREASURER).contains(&role)
+}
+
+/// Roles granted to each address, super-admins are kept in ADMINS
+pub const ROLES : Map<&Addr, Vec<u8>> = Map::new("roles");
+
 pub const LAND_NFT_COUNTER: Item<IndexCounter> = Item::new("land_nft_counter");
 
 pub const LAND_NFT_KEY_PREFIX : &str = "land_nft";
 
-pub const LAND_NFTS : Map<&str, LandNft> = Map::new("land_nfts");
+/// Secondary indexes of the land nfts, the primary key goes to the second tuple element
+pub struct LandNftIndexes<'a> {
+
+    pub status : MultiIndex<'a, (U8Key, Vec<u8>), LandNft>,
+
+    pub owner : MultiIndex<'a, (Addr, Vec<u8>), LandNft>,
+
+    pub price_denom : MultiIndex<'a, (Vec<u8>, Vec<u8>), LandNft>,
+}
+
+impl<'a> IndexList<LandNft> for LandNftIndexes<'a> {
+
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<LandNft>> + '_> {
+
+        let v : Vec<&dyn Index<LandNft>> = vec![&self.status, &self.owner, &self.price_denom];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Land nfts without a status are indexed under status 0
+pub fn land_nft_status_key(status : Option<u8>) -> U8Key {
+
+    U8Key::from(status.unwrap_or(0))
+}
+
+/// Number of land nfts per status, keyed like the status index
+pub const STATUS_COUNTS : Map<U8Key, u64> = Map::new("land_nft_status_counts");
+
+/// Sum of the total size of all land nfts
+pub const TOTAL_AREA : Item<u64> = Item::new("land_nft_total_area");
+
+/// Value of the plots still listed for sale, per denom
+pub const LISTED_VALUES : Map<&str, Uint128> = Map::new("land_nft_listed_values");
+
+/// Adds or takes away the land nft from the catalog counters
+fn update_catalog_stats(storage : &mut dyn Storage, land_nft : &LandNft, add : bool) -> StdResult<()> {
+
+    let apply = |v : u128, by : u128| if add { v + by } else { v.saturating_sub(by) };
+
+    STATUS_COUNTS.update(storage, land_nft_status_key(land_nft.status), |c| -> StdResult<u64> {
+        Ok(apply(u128::from(c.unwrap_or(0)), 1) as u64)
+    })?;
+
+    let area = TOTAL_AREA.may_load(storage)?.unwrap_or(0);
+    TOTAL_AREA.save(storage, &(apply(u128::from(area), u128::from(land_nft.total_size)) as u64))?;
+
+    let denom = land_nft.price_denom.clone().unwrap_or_default();
+    let value = u128::from(land_nft.price) * u128::from(land_nft.remaining_plot_count());
+
+    LISTED_VALUES.update(storage, denom.as_str(), |v| -> StdResult<Uint128> {
+        Ok(Uint128::from(apply(v.unwrap_or_default().u128(), value)))
+    })?;
+
+    Ok(())
+}
+
+/// Saves the land nft, keeping the catalog counters in step
+/// with the entry it replaces
+pub fn save_land_nft(storage : &mut dyn Storage, key : &str, land_nft : &LandNft) -> StdResult<()> {
+
+    if let Some(old) = land_nfts().may_load(storage, key)? {
+        update_catalog_stats(storage, &old, false)?;
+    }
+
+    update_catalog_stats(storage, land_nft, true)?;
+
+    land_nfts().save(storage, key, land_nft)
+}
+
+/// Removes the land nft and takes it out of the catalog counters
+pub fn remove_land_nft_entry(storage : &mut dyn Storage, key : &str) -> StdResult<()> {
+
+    if let Some(old) = land_nfts().may_load(storage, key)? {
+        update_catalog_stats(storage, &old, false)?;
+    }
+
+    land_nfts().remove(storage, key)
+}
+
+pub fn land_nfts<'a>() -> IndexedMap<'a, &'a str, LandNft, LandNftIndexes<'a>> {
+
+    let indexes = LandNftIndexes {
+
+        status : MultiIndex::new(|d : &LandNft, k : Vec<u8>| (land_nft_status_key(d.status), k), 
+            "land_nfts", "land_nfts__status"),
+
+        owner : MultiIndex::new(|d : &LandNft, k : Vec<u8>| (d.owner.clone(), k), 
+            "land_nfts", "land_nfts__owner"),
+
+        price_denom : MultiIndex::new(|d : &LandNft, k : Vec<u8>| (d.price_denom.clone().unwrap_or_default().into_bytes(), k), 
+            "land_nfts", "land_nfts__price_denom"),
+    };
+
+    IndexedMap::new("land_nfts", indexes)
+}
 
 pub const LAND_NFT_STATUS_MINTED : u8 = 1;
 
 pub const LAND_NFT_STATUS_TRANSFERRED : u8 = 2;
 
+pub const LAND_NFT_STATUS_BURNED : u8 = 3;
+
+pub const LAND_NFT_STATUS_RESERVED : u8 = 4;
+
 pub const DEFAULT_LAND_NFT_SYMBOL : &str = "neworld-land-nft";
 
+pub const DEFAULT_EXTERN_URL_PREFIX : &str = "https://neworld.techchee.com/land-nft";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+
+    pub name : String,
+
+    pub symbol : String,
+
+    pub price_denom : String,
+
+    pub external_url_prefix : String,
+
+    pub minter : Addr,
+
+    pub treasuries : Vec<Treasury>,
+
+    /// cap on the sum of royalties of each land nft, in basis points
+    pub max_royalty_bps : u16,
+
+    /// sale phases open minting to everyone, minters mint regardless of them
+    #[serde(default)]
+    pub sale_phases : Vec<SalePhase>,
+
+    /// reservations are disabled until terms are set
+    #[serde(default)]
+    pub reservation_terms : Option<ReservationTerms>,
+}
+
+/// What it takes to hold an unminted land nft before paying it in full
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReservationTerms {
+
+    /// deposit in basis points of the land nft's price at the time of the reservation
+    pub deposit_bps : u16,
+
+    /// seconds the reservation lasts
+    pub duration : u64,
+
+    /// whether an expired reservation's deposit goes to the treasuries instead of back to the reserver
+    pub forfeit : bool,
+}
+
+/// A window of time in which minting is open, optionally to allowlisted addresses only
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SalePhase {
+
+    pub name : String,
+
+    pub start : Timestamp,
+
+    pub end : Timestamp,
+
+    pub has_allowlist : bool,
+
+    /// plots each wallet can mint in this phase
+    pub mint_cap : Option<u32>,
+
+    /// replaces the price of the land nfts in this phase
+    pub price : Option<Uint128>,
+
+    /// hex sha256 root of an off-chain allowlist of "address,allocation" leaves,
+    /// a valid proof lets the address mint up to its allocation
+    #[serde(default)]
+    pub merkle_root : Option<String>,
+}
+
+impl SalePhase {
+
+    pub fn is_active(&self, now : Timestamp) -> bool {
+
+        now >= self.start && now < self.end
+    }
+}
+
+impl Config {
+
+    pub fn active_sale_phase(&self, now : Timestamp) -> Option<&SalePhase> {
+
+        self.sale_phases.iter().find(|p| p.is_active(now))
+    }
+}
+
+/// Prefix of the denom of a cw20 token in prices and pending balances, 
+/// followed by the address of the token's contract
+pub const CW20_DENOM_PREFIX : &str = "cw20:";
+
+pub fn cw20_denom(contract : &Addr) -> String {
+
+    format!("{}{}", CW20_DENOM_PREFIX, contract)
+}
+
+/// The address of the cw20 contract of a cw20 denom, none for native denoms
+pub fn cw20_contract(denom : &str) -> Option<&str> {
+
+    denom.strip_prefix(CW20_DENOM_PREFIX)
+}
+
+/// Price of a land nft in each cw20 token it accepts, keyed by (land nft key, cw20 contract)
+pub const CW20_PRICES : Map<(&str, &Addr), Uint128> = Map::new("cw20_prices");
+
+/// Addresses allowed to mint in a sale phase, keyed by (phase name, address)
+pub const ALLOWLIST : Map<(&str, &Addr), bool> = Map::new("allowlist");
+
+/// Plots minted by each wallet in a sale phase, keyed by (phase name, address)
+pub const PHASE_MINTS : Map<(&str, &Addr), u32> = Map::new("phase_mints");
+
+pub const CONFIG : Item<Config> = Item::new("config");
+
+/// Amounts owed to treasuries and royalty creators, keyed by (beneficiary, denom)
+pub const PENDING_BALANCES : Map<(&Addr, &str), Uint128> = Map::new("pending_balances");
+
+pub const DUTCH_AUCTION_LINEAR : u8 = 1;
+
+pub const DUTCH_AUCTION_STEPPED : u8 = 2;
+
+/// Price of a land nft that decays over time from its start price down to a floor
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DutchAuction {
+
+    pub mode : u8,
+
+    pub start_price : Uint128,
+
+    pub floor : Uint128,
+
+    pub start : Timestamp,
+
+    /// seconds it takes the price to drop by decay_step
+    pub decay_interval : u64,
+
+    pub decay_step : Uint128,
+}
+
+pub fn is_valid_dutch_auction_mode(mode : u8) -> bool {
+
+    mode == DUTCH_AUCTION_LINEAR || mode == DUTCH_AUCTION_STEPPED
+}
+
+impl DutchAuction {
+
+    pub fn price_at(&self, now : Timestamp) -> Uint128 {
+
+        let elapsed = now.seconds().saturating_sub(self.start.seconds());
+
+        // whole steps saturate so a long running auction settles at its floor
+        let steps = self.decay_step.saturating_mul(Uint128::from(elapsed / self.decay_interval));
+
+        let decay = if self.mode == DUTCH_AUCTION_STEPPED { steps }
+        else {
+            steps.saturating_add(self.decay_step.multiply_ratio(elapsed % self.decay_interval, self.decay_interval))
+        };
+
+        self.start_price.saturating_sub(decay).max(self.floor)
+    }
+}
+
+/// Dutch auction applying to every land nft without one of its own
+pub const DEFAULT_DUTCH_AUCTION : Item<Option<DutchAuction>> = Item::new("default_dutch_auction");
+
+pub const DUTCH_AUCTIONS : Map<&str, DutchAuction> = Map::new("dutch_auctions");
+
+/// The live price of a land nft, decayed by its dutch auction if it has one
+pub fn land_nft_price(storage : &dyn Storage, land_nft : &LandNft, now : Timestamp) -> StdResult<Uint128> {
+
+    let key = land_nft.key.clone().unwrap_or_default();
+
+    let auction = match DUTCH_AUCTIONS.may_load(storage, key.as_str())? {
+
+        Some(a) => Some(a),
+
+        None => DEFAULT_DUTCH_AUCTION.may_load(storage)?.flatten(),
+    };
+
+    Ok(match auction {
+
+        Some(a) => a.price_at(now),
+
+        None => Uint128::from(land_nft.price),
+    })
+}
+
+/// Bids placed this close to the end of an auction push its end back by as much, in seconds
+pub const AUCTION_TIME_EXTENSION : u64 = 300;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Bid {
+
+    pub bidder : Addr,
+
+    pub amount : Uint128,
+
+    pub date_placed : Timestamp,
+}
+
+/// An English auction of the next available plot of a land nft
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Auction {
+
+    pub for_key : String,
+
+    pub reserve_price : Uint128,
+
+    pub denom : String,
+
+    pub start : Timestamp,
+
+    pub end : Timestamp,
+
+    pub min_increment : Uint128,
+
+    /// all bids in the order they were placed, the last one is the highest
+    pub bids : Vec<Bid>,
+
+    pub settled : bool,
+}
+
+impl Auction {
+
+    pub fn highest_bid(&self) -> Option<&Bid> {
+
+        self.bids.last()
+    }
+
+    /// Lowest amount the next bid has to reach
+    pub fn minimum_bid(&self) -> Uint128 {
+
+        match self.highest_bid() {
+
+            Some(b) => b.amount + self.min_increment.max(Uint128::from(1u128)),
+
+            None => self.reserve_price.max(Uint128::from(1u128)),
+        }
+    }
+
+    pub fn is_open(&self, now : Timestamp) -> bool {
+
+        !self.settled && now >= self.start && now < self.end
+    }
+}
+
+pub const AUCTIONS : Map<&str, Auction> = Map::new("auctions");
+
+/// A land nft held for a reserver, the deposit is taken off the price when they mint it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Reservation {
+
+    pub for_key : String,
+
+    pub reserver : Addr,
+
+    pub deposit : Coin,
+
+    pub expires : Timestamp,
+
+    /// the terms' forfeit at the time of the reservation
+    pub forfeit : bool,
+
+    pub date_reserved : Timestamp,
+}
+
+impl Reservation {
+
+    pub fn is_expired(&self, now : Timestamp) -> bool {
+
+        now >= self.expires
+    }
+}
+
+pub const RESERVATIONS : Map<&str, Reservation> = Map::new("reservations");
+
+/// Lets buyers pay for a plot of a land nft over time, the amounts 
+/// add up to its price for them, in the land nft's price denom
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PaymentPlan {
+
+    /// one amount per installment, the first one is paid upfront
+    pub amounts : Vec<Uint128>,
+
+    /// seconds between the due dates of the installments
+    pub interval : u64,
+
+    /// seconds after a missed due date before the plot can be repossessed
+    pub grace_period : u64,
+}
+
+impl PaymentPlan {
+
+    pub fn is_valid(&self) -> bool {
+
+        !self.amounts.is_empty() && self.amounts.iter().all(|a| !a.is_zero()) && self.interval > 0
+    }
+}
+
+pub const PAYMENT_PLANS : Map<&str, PaymentPlan> = Map::new("payment_plans");
+
+/// A plot being paid over time, its token is held by the contract until the last installment
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstallmentPlan {
+
+    pub token_id : String,
+
+    pub buyer : Addr,
+
+    pub denom : String,
+
+    /// the payment plan at the time the buyer started it
+    pub plan : PaymentPlan,
+
+    /// number of installments paid so far
+    pub paid : u32,
+
+    pub amount_paid : Uint128,
+
+    pub date_started : Timestamp,
+}
+
+impl InstallmentPlan {
+
+    pub fn next_amount(&self) -> Option<Uint128> {
+
+        self.plan.amounts.get(self.paid as usize).copied()
+    }
+
+    pub fn next_due(&self) -> Timestamp {
+
+        self.date_started.plus_seconds(self.plan.interval * u64::from(self.paid))
+    }
+
+    pub fn is_overdue(&self, now : Timestamp) -> bool {
+
+        self.next_amount().is_some() && now >= self.next_due().plus_seconds(self.plan.grace_period)
+    }
+}
+
+/// Installment plans in progress, keyed by token id
+pub const INSTALLMENT_PLANS : Map<&str, InstallmentPlan> = Map::new("installment_plans");
+
+/// A minted plot put up for resale, the token is escrowed by the contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Listing {
+
+    pub token_id : String,
+
+    pub seller : Addr,
+
+    pub price : Uint128,
+
+    pub denom : String,
+
+    pub expires : Option<Expiration>,
+
+    pub date_listed : Timestamp,
+}
+
+pub struct ListingIndexes<'a> {
+
+    pub denom : MultiIndex<'a, (Vec<u8>, Vec<u8>), Listing>,
+
+    pub seller : MultiIndex<'a, (Addr, Vec<u8>), Listing>,
+}
+
+impl<'a> IndexList<Listing> for ListingIndexes<'a> {
+
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Listing>> + '_> {
+
+        let v : Vec<&dyn Index<Listing>> = vec![&self.denom, &self.seller];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn listings<'a>() -> IndexedMap<'a, &'a str, Listing, ListingIndexes<'a>> {
+
+    let indexes = ListingIndexes {
+
+        denom : MultiIndex::new(|d : &Listing, k : Vec<u8>| (d.denom.clone().into_bytes(), k), 
+            "listings", "listings__denom"),
+
+        seller : MultiIndex::new(|d : &Listing, k : Vec<u8>| (d.seller.clone(), k), 
+            "listings", "listings__seller"),
+    };
+
+    IndexedMap::new("listings", indexes)
+}
+
 pub fn default_unit_size ()-> String {
 
     format!("m{}",from_u32(0xb0 + 2).unwrap())
@@ -144,6 +668,12 @@ pub struct LandNft {
 
     pub status : Option<u8>,
 
+    /// number of plots minted so far, each plot is priced at `price`
+    pub minted_plots : Option<u16>,
+
+    /// plots whose tokens were burned, these are never minted again
+    pub burned_plots : Option<Vec<u16>>,
+
     pub symbol : String, 
 
     pub (crate) media_types : Option<Vec<LandNftMediaType>>,
@@ -228,6 +758,7 @@ impl LandNft {
 
 impl LandNft {
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new( 
         key : Option<String>, 
         name : Option<String>,
@@ -251,14 +782,14 @@ impl LandNft {
         let new_land = LandNft { key :key.clone(), 
             name : Some(name.unwrap_or(format!("Neworld Land NFT #{}", key.clone().unwrap_or("unknown.key".to_string())))) , 
             description :  Some(description.unwrap_or(format!("Neworld Land NFT #{}",  key.unwrap_or("unknown.key".to_string())))), 
-            owner : owner, total_size : total_size,
-            each_size : Some(each_size), size_unit : size_unit,  
-            addr: Some(addr), total_lands : total_lands, price : price, 
-            price_denom: pdenom, status : None, symbol : DEFAULT_LAND_NFT_SYMBOL.to_string(),
+            owner, total_size,
+            each_size : Some(each_size), size_unit,  
+            addr: Some(addr), total_lands, price, 
+            price_denom: pdenom, status : None, minted_plots : None, burned_plots : None, symbol : DEFAULT_LAND_NFT_SYMBOL.to_string(),
             media_types : None, royalties : None, other_attributes : None, 
-            date_created : date_created, date_updated : date_created  };
+            date_created, date_updated : date_created  };
         
-        return new_land;
+        new_land
 
     }
 
@@ -277,12 +808,86 @@ impl LandNft {
 
 }
 
+impl LandNft {
+
+    /// A parcel always has at least one plot to mint
+    pub fn plot_count(&self) -> u16 {
+
+        self.total_lands.max(1)
+    }
+
+    pub fn minted_plot_count(&self) -> u16 {
+
+        self.minted_plots.unwrap_or(0)
+    }
+
+    pub fn remaining_plot_count(&self) -> u16 {
+
+        self.plot_count().saturating_sub(self.minted_plot_count())
+    }
+
+    pub fn burned_plot_count(&self) -> u16 {
+
+        self.burned_plots.as_ref().map(|p| p.len() as u16).unwrap_or(0)
+    }
+
+    pub fn is_plot_burned(&self, plot : u16) -> bool {
+
+        self.burned_plots.as_ref().map(|p| p.contains(&plot)).unwrap_or(false)
+    }
+
+    /// Records the plot as burned, the parcel is marked burned
+    /// once none of its plots is left
+    pub fn burn_plot(&mut self, plot : u16, date_updated : Timestamp) {
+
+        let mut burned = self.burned_plots.clone().unwrap_or_default();
+
+        if !burned.contains(&plot) {
+            burned.push(plot);
+        }
+
+        self.burned_plots = Some(burned);
+
+        if self.burned_plot_count() >= self.plot_count() {
+            self.status = Some(LAND_NFT_STATUS_BURNED);
+        }
+
+        self.date_updated = date_updated;
+    }
+
+    pub fn token_id(key : &str, plot : u16) -> String {
+
+        format!("{}/{}", key, plot)
+    }
+
+    /// Splits a plot token id back into its parcel key and plot number
+    pub fn parse_token_id(token_id : &str) -> Option<(String, u16)> {
+
+        let (key, plot) = token_id.rsplit_once('/')?;
+
+        Some((key.to_string(), plot.parse::<u16>().ok()?))
+    }
+
+    pub fn to_plot_metadata_attributes(&self, plot : u16) -> Vec<Trait> {
+
+        let mut traits = self.to_metadata_attributes();
+
+        traits.push( Trait {
+            display_type : Some("Plot".to_string()),
+            trait_type : "plot".to_string(),
+            value : format!("{} of {}", plot, self.plot_count())
+        });
+
+        traits
+    }
+}
+
 impl LandNft{
     pub fn add_media_type(&mut self, media_type : LandNftMediaType, date_updated : Timestamp ){
 
         self.date_updated = date_updated;
 
-        if self.media_types == None {
+        if self.media_types.is_none() {
             let v :Vec<LandNftMediaType> = Vec::new();
             self.media_types = Some(v);
         }
@@ -302,8 +907,8 @@ impl LandNft{
                 
             let pos : Option<usize> = vector.iter().position(|m| *m.url == url );
 
-            if pos.is_some() {
-                vector.remove(pos.unwrap());
+            if let Some(pos) = pos {
+                vector.remove(pos);
             }
         }
     }
@@ -321,7 +926,7 @@ impl LandNft{
             return vector.len();
         }
 
-        return 0; 
+        0
     }
 
 }
@@ -330,37 +935,16 @@ impl LandNft {
 
     pub fn default_media_type_of(&self, media_type : u8) -> Option<LandNftMediaType>  {
 
-        if self.media_types.is_none(){
-
-            return None;
-        }
-
-        let return_media_types : Vec<LandNftMediaType> = self.media_types.clone().unwrap();
+        let return_media_types : Vec<LandNftMediaType> = self.media_types.clone()?;
 
-        let col = return_media_types.into_iter().filter(|m| {    
+        return_media_types.into_iter().find(|m| {    
             m.media_type == media_type && m.is_default 
-        }).collect::<Vec<LandNftMediaType>>();
-
-        let m = col.iter().next();
-        if m.is_some (){
-            return Some(m.unwrap().clone()); 
-        }
-        else {
-            return None;
-        }
-        
+        })
     }
 
     pub fn default_media_type_url(&self, media_type : u8) -> Option<String>  {
 
-        let m = self.default_media_type_of(media_type);
-
-        if m.is_none() {
-            return None;
-        }
-
-        return Some(m.unwrap().url);
-
+        self.default_media_type_of(media_type).map(|m| m.url)
     }
 }
 
@@ -371,7 +955,7 @@ impl LandNft {
 
         self.date_updated = date_updated;
 
-        if self.royalties == None {
+        if self.royalties.is_none() {
             let v :Vec<LandNftRoyalty> = Vec::new();
             self.royalties = Some(v);
         }
@@ -392,8 +976,8 @@ impl LandNft {
                 
             let pos : Option <usize> = vector.iter().position(|r| r.creator_wallet == creator_wallet );
 
-            if pos.is_some() {
-                vector.remove(pos.unwrap());
+            if let Some(pos) = pos {
+                vector.remove(pos);
             }
         }
     }
@@ -403,6 +987,33 @@ impl LandNft {
         self.royalties.clone()
     }
 
+    /// Sum of the royalties in basis points
+    pub fn total_royalty_bps(&self) -> u32 {
+
+        self.royalties.as_ref().map(|v| v.iter().map(|r| u32::from(r.royalty)).sum()).unwrap_or(0)
+    }
+
+    /// Royalties are paid out to the creator with the lowest index 
+    /// by marketplaces that only support a single royalty address
+    pub fn royalty_payment_address(&self) -> Option<Addr> {
+
+        self.royalties.as_ref()?.iter().min_by_key(|r| r.index).map(|r| r.creator_wallet.clone())
+    }
+
+    /// Amount owed to each royalty creator on a sale at the given price, in index order
+    pub fn royalty_shares(&self, sale_price : Uint128) -> Vec<(LandNftRoyalty, Uint128)> {
+
+        let mut royalties = self.royalties.clone().unwrap_or_default();
+
+        royalties.sort_by_key(|r| r.index);
+
+        royalties.into_iter().map(|r| {
+
+            let amount = sale_price.multiply_ratio(r.royalty, MAX_BASIS_POINTS);
+            (r, amount)
+        }).collect()
+    }
+
     pub fn royalty_count(&self) -> usize{
 
         if let Some(ref vector) = self.royalties {
@@ -410,7 +1021,7 @@ impl LandNft {
             return vector.len();
         }
 
-        return 0; 
+        0
     }
 
 }
@@ -422,7 +1033,7 @@ impl LandNft {
     pub fn add_other_attribute(&mut self, attribute : Attribute ){
 
 
-        if self.other_attributes == None {
+        if self.other_attributes.is_none() {
             let v :Vec<Attribute> = vec![];
             self.other_attributes = Some(v);
         }
@@ -441,8 +1052,8 @@ impl LandNft {
             
             let pos : Option<usize>  = vector.iter().position(|r| r.attribute_type == attribute_type );
 
-            if pos.is_some() {
-                vector.remove(pos.unwrap());
+            if let Some(pos) = pos {
+                vector.remove(pos);
             }
         } 
     
@@ -460,9 +1071,14 @@ impl LandNft {
             return vector.len();
         }
 
-        return 0; 
+        0
     }
 
 }
 
 
+/// Royalty owed on a sale at the given basis points, as answered to cw2981 marketplaces
+pub fn royalty_amount(sale_price : Uint128, royalty_bps : u32) -> Uint128 {
+
+    sale_price.multiply_ratio(royalty_bps, MAX_BASIS_POINTS)
+}
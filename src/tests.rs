@@ -1,22 +1,53 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
   
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins,  Addr, from_binary};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
+    use cosmwasm_std::{coins,  Addr, from_binary, DepsMut, BankMsg, Coin, CosmosMsg, Uint128};
     use crate::ins::*;
     use crate::state::*;
     use crate::get::*;
     use crate::msg::*;
     use crate::contract::*;
     use crate::resp::*;
+    use crate::ContractError;
+    use crate::merkle;
+    use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+    use cw20::{Cw20Coin, Cw20CoinVerified, Cw20ExecuteMsg};
+    use cosmwasm_std::{to_binary, Api, CanonicalAddr, RecoverPubkeyError, StdResult, VerificationError};
+    use cosmwasm_std::testing::MockApi;
     use std::mem::size_of;
 
+    fn setup_contract(deps : DepsMut) {
+
+        let msg = InstantiateMsg {
+            name : "Neworld Land NFT".to_string(),
+            symbol : None,
+            price_denom : None,
+            external_url_prefix : None,
+            minter : None,
+            admins : vec!["terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9".to_string(),
+            "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0".to_string(), 
+            "terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3".to_string()],
+            treasuries : vec![
+                TreasuryMsg { wallet_address : "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0".to_string(), 
+                    basis_points : 9500, is_primary : true },
+                TreasuryMsg { wallet_address : "terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3".to_string(), 
+                    basis_points : 500, is_primary : false },
+            ],
+            max_royalty_bps : None,
+        };
+
+        let info = mock_info("creator", &[]);
+        instantiate(deps, mock_env(), info, msg).expect("Failed to instantiate contract");
+    }
   
     #[test]
     fn test_filter(){
 
 
         let mut deps = mock_dependencies(&coins(2, "token"));
+        setup_contract(deps.as_mut());
         let info = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &coins(2, "token"));
        
         for n in 1..7 {
@@ -51,18 +82,24 @@ mod tests {
             l.total_size,l.owner, l.addr.unwrap_or("N/A".to_string()) );
         });
        
-        let minter = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(2, "token"));
+        let minter = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(500, "uusd"));
        
-        let msg = ExecuteMsg::InsAndMintLandNft {
+        let msg = ExecuteMsg::MintLandNft {
             for_key : "land_nft_2".to_string(),
-            external_url_prefix : None, 
+            plot : None,
+            external_url_prefix : None,
+            proof : None,
+            allocation : None,
         };
         let _ = execute(deps.as_mut(), mock_env(), minter.clone(), msg);
         
 
-        let msg = ExecuteMsg::InsAndMintLandNft {
+        let msg = ExecuteMsg::MintLandNft {
             for_key : "land_nft_4".to_string(),
-            external_url_prefix : None, 
+            plot : None,
+            external_url_prefix : None,
+            proof : None,
+            allocation : None,
         };
         let _ = execute(deps.as_mut(), mock_env(), minter, msg);
         
@@ -103,7 +140,7 @@ mod tests {
 
 
 
-        let msg = QueryMsg::AllNftInfo { token_id : "land_nft_2".to_string()};
+        let msg = QueryMsg::AllNftInfo { token_id : "land_nft_2/1".to_string()};
 
         let res = query(deps.as_ref(), mock_env(), msg).expect("failed to unwrap!!");
 
@@ -119,6 +156,7 @@ mod tests {
     fn test_land_nft(){
 
         let mut deps = mock_dependencies(&coins(2, "token"));
+        setup_contract(deps.as_mut());
         let info = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &coins(2, "token"));
        
         let add_mesg = ExecuteMsg::AddLandNft {
@@ -171,11 +209,15 @@ mod tests {
         println!("get.res.value:: {:?}", value);
 
 
-        let test_minting_msg = ExecuteMsg::InsAndMintLandNft {
+        let test_minting_msg = ExecuteMsg::MintLandNft {
             for_key : key.clone(),
-            external_url_prefix : None, 
+            plot : None,
+            external_url_prefix : None,
+            proof : None,
+            allocation : None,
         };
-        let res = execute(deps.as_mut(), mock_env(), info.clone(), test_minting_msg);
+        let buyer = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &coins(35600, "uusd"));
+        let res = execute(deps.as_mut(), mock_env(), buyer, test_minting_msg);
         println!("\ntesting.minting.res::{:?}", res);
 
         
@@ -201,7 +243,7 @@ mod tests {
         println!("\n\nres.all.minted:: {:?}", value);
 
 
-        let msg = QueryMsg::AllNftInfo { token_id : key.clone()};
+        let msg = QueryMsg::AllNftInfo { token_id : LandNft::token_id(key.as_str(), 1)};
         let res = query(deps.as_ref(), mock_env(), msg).expect("failed to unwrap res of all nft info");
         let value : cw721::AllNftInfoResponse<Metadata> = from_binary(&res).expect("failed to unwrap binary");
         println!("\n\nres.all.nft.info :: {:?}", value);
@@ -211,7 +253,7 @@ mod tests {
         let res = query(deps.as_ref(), mock_env(), msg).expect("failed to unwrap res of minted tokens by owner");
         let value : cw721::TokensResponse = from_binary(&res).expect("Failed to unwrap binary!!");
        
-        println!("\n\nres.minted.tokens.by_owner:{}:: {:?}", info.sender.to_string(), value);
+        println!("\n\nres.minted.tokens.by_owner:{}:: {:?}", info.sender, value);
 
 
     }
@@ -220,6 +262,7 @@ mod tests {
     fn test_add_land_nfts(){
 
         let mut deps = mock_dependencies(&coins(2, "token"));
+        setup_contract(deps.as_mut());
         let info = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &coins(2, "token"));
        
 
@@ -294,7 +337,7 @@ mod tests {
                
                 creator_wallet : Addr::unchecked("terra1qchccaxyrzk8a4yxu6y2vwzc48jak8qmqm9qtg"),
                 index : (n -1),
-                royalty: 10000, 
+                royalty: 500, 
                 date_updated : None, 
             });
         }
@@ -310,38 +353,1841 @@ mod tests {
 
 
     #[test]
-    fn test_funcs(){
+    fn test_admins(){
 
-        let mut nft = LandNft::default(mock_env().block.time);
-        
-        for n in 1..4 {
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
 
-            let mut is_def = false ;
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+        let outsider = mock_info("terra1qchccaxyrzk8a4yxu6y2vwzc48jak8qmqm9qtg", &[]);
 
-            if n == 2 {
-                is_def = true ;
+        let msg = ExecuteMsg::AddAdmin { address : outsider.sender.to_string() };
+        let res = execute(deps.as_mut(), mock_env(), outsider.clone(), msg.clone());
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), msg);
+        assert!(res.is_ok());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ListAdmins {}).expect("Failed to unwrap res!!!");
+        let value : AdminsResponse = from_binary(&res).unwrap();
+        assert_eq!(4, value.admins.len());
+        assert!(value.admins.contains(&outsider.sender));
+
+        // the newly added admin can now manage the catalog
+        let res = add_land_nft(deps.as_mut(),mock_env(), outsider.clone(), None, None, 
+        12560, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 
+        12, 2310, None );
+        assert!(res.is_ok());
+
+        let admins = value.admins;
+        for a in admins.iter().take(3) {
+
+            let msg = ExecuteMsg::RemoveAdmin { address : a.to_string() };
+            let res = execute(deps.as_mut(), mock_env(), outsider.clone(), msg);
+            assert!(res.is_ok());
+        }
+
+        let msg = ExecuteMsg::RemoveAdmin { address : outsider.sender.to_string() };
+        let res = execute(deps.as_mut(), mock_env(), outsider.clone(), msg);
+        assert!(matches!(res, Err(ContractError::CannotRemoveLastAdmin {})));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ListAdmins {}).expect("Failed to unwrap res!!!");
+        let value : AdminsResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![outsider.sender], value.admins);
+    }
+
+    #[test]
+    fn test_roles(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+        let editor = mock_info("terra1qchccaxyrzk8a4yxu6y2vwzc48jak8qmqm9qtg", &[]);
+
+        let res = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        12560, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 
+        12, 2310, None );
+        assert!(res.is_ok());
+        let key = LandNft::key(1);
+
+        let msg = ExecuteMsg::GrantRole { address : editor.sender.to_string(), role : ROLE_CATALOG_EDITOR };
+        let res = execute(deps.as_mut(), mock_env(), editor.clone(), msg.clone());
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), msg);
+        assert!(res.is_ok());
+
+        let msg = ExecuteMsg::GrantRole { address : editor.sender.to_string(), role : 99 };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), msg);
+        assert!(matches!(res, Err(ContractError::InvalidRole { role : 99 })));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::RolesOf { address : editor.sender.to_string() })
+        .expect("Failed to unwrap res!!!");
+        let value : RolesResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![ROLE_CATALOG_EDITOR], value.roles);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::RolesOf { address : admin.sender.to_string() })
+        .expect("Failed to unwrap res!!!");
+        let value : RolesResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![ROLE_SUPER_ADMIN], value.roles);
+
+        // editors may touch media types and descriptions only
+        let msg = ExecuteMsg::AddLandNftMediaType { for_key : key.clone(), 
+            url : "https://imgurl.ii/ImgX_1".to_string(), media_type : MEDIA_TYPE_IMAGE, is_default : true };
+        assert!(execute(deps.as_mut(), mock_env(), editor.clone(), msg).is_ok());
+
+        let msg = ExecuteMsg::UpdateLandNftDescription { for_key : key.clone(), 
+            name : None, description : Some("Sea facing lot".to_string()) };
+        assert!(execute(deps.as_mut(), mock_env(), editor.clone(), msg).is_ok());
+
+        let land_nft = get_land_nft(deps.as_ref(), key.clone()).unwrap().land_nft;
+        assert_eq!(Some("Sea facing lot".to_string()), land_nft.description);
+        assert_eq!(1, land_nft.media_type_count());
+
+        let msg = ExecuteMsg::AddLandNftRoyalty { for_key : key.clone(), 
+            creator_wallet : editor.sender.to_string(), index : 0, royalty : 500 };
+        let res = execute(deps.as_mut(), mock_env(), editor.clone(), msg);
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        let msg = ExecuteMsg::MintLandNft { for_key : key.clone(), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        let res = execute(deps.as_mut(), mock_env(), editor.clone(), msg);
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        let msg = ExecuteMsg::RemoveLandNft { for_key : key.clone() };
+        let res = execute(deps.as_mut(), mock_env(), editor.clone(), msg);
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        let msg = ExecuteMsg::RevokeRole { address : editor.sender.to_string(), role : ROLE_CATALOG_EDITOR };
+        assert!(execute(deps.as_mut(), mock_env(), admin.clone(), msg).is_ok());
+
+        let msg = ExecuteMsg::RemoveLandNftMediaType { for_key : key, url : "https://imgurl.ii/ImgX_1".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), editor, msg);
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+    }
+
+    #[test]
+    fn test_config(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).expect("Failed to unwrap res!!!");
+        let value : ConfigResponse = from_binary(&res).unwrap();
+        assert_eq!("Neworld Land NFT", value.config.name);
+        assert_eq!(DEFAULT_LAND_NFT_SYMBOL, value.config.symbol);
+        assert_eq!(DEFAULT_PRICE_DENOM, value.config.price_denom);
+        assert_eq!(DEFAULT_EXTERN_URL_PREFIX, value.config.external_url_prefix);
+        assert_eq!(2, value.config.treasuries.len());
+
+        let msg = ExecuteMsg::UpdateConfig { name : None, symbol : Some("nwld".to_string()), 
+            price_denom : Some("uluna".to_string()), external_url_prefix : None, minter : None, max_royalty_bps : None };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg.clone());
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+        assert!(execute(deps.as_mut(), mock_env(), admin.clone(), msg).is_ok());
+
+        let res = add_land_nft(deps.as_mut(),mock_env(), admin, None, None, 
+        12560, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 
+        12, 2310, None );
+        assert!(res.is_ok());
+
+        let land_nft = get_land_nft(deps.as_ref(), LandNft::key(1)).unwrap().land_nft;
+        assert_eq!("nwld", land_nft.symbol);
+        assert_eq!(Some("uluna".to_string()), land_nft.price_denom);
+
+        let msg = InstantiateMsg {
+            name : "Neworld Land NFT".to_string(),
+            symbol : None,
+            price_denom : None,
+            external_url_prefix : None,
+            minter : None,
+            admins : vec![],
+            treasuries : vec![
+                TreasuryMsg { wallet_address : "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0".to_string(), 
+                    basis_points : 9000, is_primary : true },
+            ],
+            max_royalty_bps : None,
+        };
+        let mut deps = mock_dependencies(&[]);
+        let res = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg);
+        assert!(matches!(res, Err(ContractError::InvalidTreasuries {})));
+    }
+
+    #[test]
+    fn test_collection_initialized_once(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+        let minter = mock_info("terra1qchccaxyrzk8a4yxu6y2vwzc48jak8qmqm9qtg", &coins(2310, "uusd"));
+
+        let contract = MyNftMintingContract::default();
+        let contract_info = contract.contract_info.load(&deps.storage).unwrap();
+        assert_eq!("Neworld Land NFT", contract_info.name);
+        assert_eq!(DEFAULT_LAND_NFT_SYMBOL, contract_info.symbol);
+        assert_eq!(Addr::unchecked("creator"), contract.minter.load(&deps.storage).unwrap());
+
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), ExecuteMsg::InstantiateMinting {});
+        assert!(matches!(res, Err(ContractError::CollectionAlreadyInitialized {})));
+
+        let res = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        12560, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 
+        12, 2310, None );
+        assert!(res.is_ok());
+
+        let msg = ExecuteMsg::GrantRole { address : minter.sender.to_string(), role : ROLE_MINTER };
+        assert!(execute(deps.as_mut(), mock_env(), admin.clone(), msg).is_ok());
+
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        let res = execute(deps.as_mut(), mock_env(), minter.clone(), msg);
+        assert!(res.is_ok());
+
+        // minting leaves the collection minter as configured
+        assert_eq!(Addr::unchecked("creator"), contract.minter.load(&deps.storage).unwrap());
+
+        let msg = QueryMsg::MintedTokensByOwner{ owner : minter.sender.to_string(), start_after : None, limit : None};
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value : cw721::TokensResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![LandNft::token_id(LandNft::key(1).as_str(), 1)], value.tokens);
+
+        let msg = ExecuteMsg::UpdateConfig { name : None, symbol : None, 
+            price_denom : None, external_url_prefix : None, minter : Some(minter.sender.to_string()), max_royalty_bps : None };
+        assert!(execute(deps.as_mut(), mock_env(), admin, msg).is_ok());
+        assert_eq!(minter.sender, contract.minter.load(&deps.storage).unwrap());
+
+        // the role moves with the minter
+        let roles_of = |deps : cosmwasm_std::Deps, address : &str| {
+            let res = query(deps, mock_env(), QueryMsg::RolesOf { address : address.to_string() }).unwrap();
+            from_binary::<RolesResponse>(&res).unwrap().roles
+        };
+        assert_eq!(vec![ROLE_MINTER], roles_of(deps.as_ref(), minter.sender.as_str()));
+        assert!(roles_of(deps.as_ref(), "creator").is_empty());
+    }
+
+    #[test]
+    fn test_mint_payment(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let res = add_land_nft(deps.as_mut(),mock_env(), admin, None, None, 
+        12560, 12560, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 
+        1, 2310, None );
+        assert!(res.is_ok());
+
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : None, external_url_prefix : None, proof : None, allocation : None };
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &[]);
+        let res = execute(deps.as_mut(), mock_env(), buyer, msg.clone());
+        assert!(matches!(res, Err(ContractError::InsufficientFunds { .. })));
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(2309, "uusd"));
+        let res = execute(deps.as_mut(), mock_env(), buyer, msg.clone());
+        assert!(matches!(res, Err(ContractError::InsufficientFunds { .. })));
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(2310, "uluna"));
+        let res = execute(deps.as_mut(), mock_env(), buyer, msg.clone());
+        assert!(matches!(res, Err(ContractError::WrongDenom { .. })));
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(3000, "uusd"));
+        let res = execute(deps.as_mut(), mock_env(), buyer.clone(), msg.clone()).unwrap();
+
+        let refund = CosmosMsg::Bank(BankMsg::Send {
+            to_address : buyer.sender.to_string(),
+            amount : coins(690, "uusd"),
+        });
+        assert!(res.messages.iter().any(|m| m.msg == refund));
+
+        let res = execute(deps.as_mut(), mock_env(), buyer, msg);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_mint_credits_treasuries(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+        let treasury = mock_info("terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0", &[]);
+
+        let res = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        12560, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 
+        12, 2000, Some("uluna".to_string()) );
+        assert!(res.is_ok());
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(2500, "uluna"));
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        let res = execute(deps.as_mut(), mock_env(), buyer.clone(), msg).unwrap();
+
+        // only the overpayment leaves the contract, treasuries claim their share later
+        let msgs = res.messages.into_iter().map(|m| m.msg).collect::<Vec<CosmosMsg>>();
+        assert_eq!(vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address : buyer.sender.to_string(),
+                amount : coins(500, "uluna"),
+            }),
+        ], msgs);
+
+        let msg = QueryMsg::PendingBalance { address : treasury.sender.to_string() };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value : PendingBalanceResponse = from_binary(&res).unwrap();
+        assert_eq!(coins(1900, "uluna"), value.balances);
+
+        let value = get_pending_balance(deps.as_ref(), admin.sender.to_string()).unwrap();
+        assert_eq!(coins(100, "uluna"), value.balances);
+
+        let msg = ExecuteMsg::Withdraw { denom : "uluna".to_string(), amount : Uint128::new(2000) };
+        let res = execute(deps.as_mut(), mock_env(), treasury.clone(), msg);
+        assert!(matches!(res, Err(ContractError::InsufficientPendingBalance { .. })));
+
+        let msg = ExecuteMsg::Withdraw { denom : "uluna".to_string(), amount : Uint128::new(1500) };
+        let res = execute(deps.as_mut(), mock_env(), treasury.clone(), msg).unwrap();
+        assert_eq!(CosmosMsg::Bank(BankMsg::Send {
+            to_address : treasury.sender.to_string(),
+            amount : coins(1500, "uluna"),
+        }), res.messages[0].msg);
+
+        let value = get_pending_balance(deps.as_ref(), treasury.sender.to_string()).unwrap();
+        assert_eq!(coins(400, "uluna"), value.balances);
+
+        let msg = ExecuteMsg::Withdraw { denom : "uluna".to_string(), amount : Uint128::new(400) };
+        assert!(execute(deps.as_mut(), mock_env(), treasury.clone(), msg).is_ok());
+
+        let value = get_pending_balance(deps.as_ref(), treasury.sender.to_string()).unwrap();
+        assert!(value.balances.is_empty());
+    }
+
+    #[test]
+    fn test_treasury_splits(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+        let treasurer = mock_info("terra1qchccaxyrzk8a4yxu6y2vwzc48jak8qmqm9qtg", &[]);
+
+        let config = get_config(deps.as_ref()).unwrap().config;
+
+        // 95% and 5% of 2311 leave one unit of dust for the primary treasury
+        let shares = crate::ins::treasury_shares(&config.treasuries, Uint128::new(2311)).unwrap();
+        assert_eq!(vec![
+            (Addr::unchecked("terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0"), Uint128::new(2196)),
+            (Addr::unchecked("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3"), Uint128::new(115)),
+        ], shares);
+
+        let treasuries = vec![
+            TreasuryMsg { wallet_address : "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0".to_string(), 
+                basis_points : 3333, is_primary : false },
+            TreasuryMsg { wallet_address : "terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3".to_string(), 
+                basis_points : 3333, is_primary : false },
+            TreasuryMsg { wallet_address : treasurer.sender.to_string(), 
+                basis_points : 3334, is_primary : true },
+        ];
+
+        let msg = ExecuteMsg::SetTreasuries { treasuries : treasuries.clone() };
+        let res = execute(deps.as_mut(), mock_env(), treasurer.clone(), msg.clone());
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        let grant = ExecuteMsg::GrantRole { address : treasurer.sender.to_string(), role : ROLE_TREASURER };
+        assert!(execute(deps.as_mut(), mock_env(), admin, grant).is_ok());
+        assert!(execute(deps.as_mut(), mock_env(), treasurer.clone(), msg).is_ok());
+
+        let mut invalid = treasuries.clone();
+        invalid[0].basis_points = 3000;
+        let msg = ExecuteMsg::SetTreasuries { treasuries : invalid };
+        let res = execute(deps.as_mut(), mock_env(), treasurer.clone(), msg);
+        assert!(matches!(res, Err(ContractError::InvalidTreasuries {})));
+
+        let mut invalid = treasuries;
+        invalid[0].is_primary = true;
+        let msg = ExecuteMsg::SetTreasuries { treasuries : invalid };
+        let res = execute(deps.as_mut(), mock_env(), treasurer.clone(), msg);
+        assert!(matches!(res, Err(ContractError::InvalidTreasuries {})));
+
+        let msg = ExecuteMsg::SetTreasuries { treasuries : vec![] };
+        let res = execute(deps.as_mut(), mock_env(), treasurer.clone(), msg);
+        assert!(matches!(res, Err(ContractError::InvalidTreasuries {})));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Treasuries {}).unwrap();
+        let value : TreasuriesResponse = from_binary(&res).unwrap();
+        assert_eq!(3, value.treasuries.len());
+
+        let shares = crate::ins::treasury_shares(&value.treasuries, Uint128::new(100)).unwrap();
+        let amounts = shares.iter().map(|(_, amount)| amount.u128()).collect::<Vec<u128>>();
+        assert_eq!(vec![33, 33, 34], amounts);
+    }
+
+    #[test]
+    fn test_mint_plots(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(100, "uusd"));
+
+        let res = add_land_nft(deps.as_mut(),mock_env(), admin, None, None, 
+        150, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 
+        3, 100, None );
+        assert!(res.is_ok());
+
+        let key = LandNft::key(1);
+
+        let msg = ExecuteMsg::MintLandNft { for_key : key.clone(), plot : Some(2), external_url_prefix : None, proof : None, allocation : None };
+        let res = execute(deps.as_mut(), mock_env(), buyer.clone(), msg.clone()).unwrap();
+        assert!(res.attributes.iter().any(|a| a.key == "token_id" && a.value == "land_nft_1/2"));
+
+        let res = execute(deps.as_mut(), mock_env(), buyer.clone(), msg);
+        assert!(matches!(res, Err(ContractError::PlotNotAvailable { .. })));
+
+        let msg = ExecuteMsg::MintLandNft { for_key : key.clone(), plot : Some(4), external_url_prefix : None, proof : None, allocation : None };
+        let res = execute(deps.as_mut(), mock_env(), buyer.clone(), msg);
+        assert!(matches!(res, Err(ContractError::PlotNotAvailable { .. })));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::RemainingPlots { for_key : key.clone() }).unwrap();
+        let value : RemainingPlotsResponse = from_binary(&res).unwrap();
+        assert_eq!((3, 1, 2), (value.total_plots, value.minted_plots, value.remaining_plots));
+
+        let land_nft = get_land_nft(deps.as_ref(), key.clone()).unwrap().land_nft;
+        assert_eq!(None, land_nft.status);
+
+        let msg = ExecuteMsg::MintLandNft { for_key : key.clone(), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        let res = execute(deps.as_mut(), mock_env(), buyer.clone(), msg.clone()).unwrap();
+        assert!(res.attributes.iter().any(|a| a.key == "token_id" && a.value == "land_nft_1/3"));
+
+        let res = execute(deps.as_mut(), mock_env(), buyer.clone(), msg.clone()).unwrap();
+        assert!(res.attributes.iter().any(|a| a.key == "token_id" && a.value == "land_nft_1/1"));
+
+        let land_nft = get_land_nft(deps.as_ref(), key.clone()).unwrap().land_nft;
+        assert_eq!(Some(LAND_NFT_STATUS_MINTED), land_nft.status);
+        assert_eq!(0, land_nft.remaining_plot_count());
+
+        let res = execute(deps.as_mut(), mock_env(), buyer, msg);
+        assert!(res.is_err());
+
+        let msg = QueryMsg::AllNftInfo { token_id : "land_nft_1/3".to_string()};
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value : cw721::AllNftInfoResponse<Metadata> = from_binary(&res).unwrap();
+        let attributes = value.info.extension.attributes.unwrap();
+        assert!(attributes.iter().any(|t| t.trait_type == "unit-size" && t.value == "50 m\u{b2}"));
+        assert!(attributes.iter().any(|t| t.trait_type == "plot" && t.value == "3 of 3"));
+
+        assert_eq!(Some(("land_nft_1".to_string(), 3)), LandNft::parse_token_id("land_nft_1/3"));
+        assert_eq!(None, LandNft::parse_token_id("land_nft_1"));
+    }
+
+    #[test]
+    fn test_batch_mint(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 2, 100, None );
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 100, None,  "Tmn Sinar Bak Bak, Lot 91".to_string(), 1, 250, None );
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin, None, None, 
+        100, 100, None,  "Tmn Sinar Bak Bak, Lot 92".to_string(), 1, 40, Some("uluna".to_string()) );
+
+        let keys = vec![LandNft::key(1), LandNft::key(1), LandNft::key(2), LandNft::key(3)];
+        let msg = ExecuteMsg::BatchMintLandNft { keys : keys.clone(), external_url_prefix : None, proof : None, allocation : None };
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(450, "uusd"));
+        let res = execute(deps.as_mut(), mock_env(), buyer, msg.clone());
+        assert!(matches!(res, Err(ContractError::InsufficientFunds { .. })));
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", 
+        &[Coin::new(449, "uusd"), Coin::new(40, "uluna")]);
+        let res = execute(deps.as_mut(), mock_env(), buyer, msg.clone());
+        assert!(matches!(res, Err(ContractError::InsufficientFunds { .. })));
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", 
+        &[Coin::new(460, "uusd"), Coin::new(40, "uluna")]);
+        let res = execute(deps.as_mut(), mock_env(), buyer.clone(), msg.clone()).unwrap();
+
+        let token_ids = res.attributes.iter().find(|a| a.key == "token_ids").unwrap().value.clone();
+        assert_eq!("land_nft_1/1,land_nft_1/2,land_nft_2/1,land_nft_3/1", token_ids);
+
+        assert_eq!(CosmosMsg::Bank(BankMsg::Send {
+            to_address : buyer.sender.to_string(),
+            amount : coins(10, "uusd"),
+        }), res.messages[0].msg);
+
+        let value = get_pending_balance(deps.as_ref(), "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0".to_string()).unwrap();
+        assert_eq!(vec![Coin::new(38, "uluna"), Coin::new(428, "uusd")], value.balances);
+
+        // everything is sold out now, so the same batch fails as a whole
+        let res = execute(deps.as_mut(), mock_env(), buyer, msg);
+        assert!(res.is_err());
+
+        let msg = QueryMsg::NumOfMintedTokens {};
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value : cw721::NumTokensResponse = from_binary(&res).unwrap();
+        assert_eq!(4, value.count);
+    }
+
+    #[test]
+    fn test_transfer_plots(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 2, 100, None );
+
+        let buyer = "terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9";
+        let other = "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0";
+
+        let msg = ExecuteMsg::BatchMintLandNft { keys : vec![LandNft::key(1), LandNft::key(1)], external_url_prefix : None, proof : None, allocation : None };
+        execute(deps.as_mut(), mock_env(), mock_info(buyer, &coins(200, "uusd")), msg).unwrap();
+
+        // only the holder or an approved spender may transfer
+        let msg = ExecuteMsg::TransferNft { recipient : other.to_string(), token_id : "land_nft_1/1".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(other, &[]), msg.clone());
+        assert!(matches!(res, Err(ContractError::Cw721(cw721_base::ContractError::Unauthorized {}))));
+
+        let approve = ExecuteMsg::Approve { spender : other.to_string(), token_id : "land_nft_1/1".to_string(), expires : None };
+        execute(deps.as_mut(), mock_env(), mock_info(buyer, &[]), approve).unwrap();
+
+        execute(deps.as_mut(), mock_env(), mock_info(other, &[]), msg).unwrap();
+
+        let value = get_land_nft(deps.as_ref(), LandNft::key(1)).unwrap().land_nft;
+        assert_eq!(Some(LAND_NFT_STATUS_TRANSFERRED), value.status);
+        assert_eq!(Addr::unchecked(buyer), value.owner);
+
+        // the parcel follows once every plot is held by the same owner
+        let msg = ExecuteMsg::TransferNft { recipient : other.to_string(), token_id : "land_nft_1/2".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info(buyer, &[]), msg).unwrap();
+
+        let value = get_land_nft(deps.as_ref(), LandNft::key(1)).unwrap().land_nft;
+        assert_eq!(Addr::unchecked(other), value.owner);
+
+        let msg = ExecuteMsg::Revoke { spender : buyer.to_string(), token_id : "land_nft_1/2".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info(other, &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::ApproveAll { operator : buyer.to_string(), expires : None };
+        execute(deps.as_mut(), mock_env(), mock_info(other, &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::RevokeAll { operator : buyer.to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info(other, &[]), msg).unwrap();
+    }
+
+    #[test]
+    fn test_burn(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 2, 100, None );
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(100, "uusd"));
+        let stranger = mock_info("stranger", &[]);
+
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : Some(1), external_url_prefix : None, proof : None, allocation : None };
+        execute(deps.as_mut(), mock_env(), buyer.clone(), msg).unwrap();
+
+        // the parcel can't be removed while a plot token is live
+        let msg = ExecuteMsg::RemoveLandNft { for_key : LandNft::key(1) };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), msg);
+        assert!(matches!(res, Err(ContractError::LandNftHasLiveToken { .. })));
+
+        let burn = ExecuteMsg::BurnLandNft { token_id : "land_nft_1/1".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), stranger, burn.clone());
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        execute(deps.as_mut(), mock_env(), buyer.clone(), burn).unwrap();
+
+        let value = get_land_nft(deps.as_ref(), LandNft::key(1)).unwrap().land_nft;
+        assert_eq!(Some(vec![1]), value.burned_plots);
+        assert_eq!(None, value.status);
+
+        // a burned plot is never minted again
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : Some(1), external_url_prefix : None, proof : None, allocation : None };
+        let res = execute(deps.as_mut(), mock_env(), buyer.clone(), msg);
+        assert!(matches!(res, Err(ContractError::PlotNotAvailable { .. })));
+
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        let res = execute(deps.as_mut(), mock_env(), buyer, msg).unwrap();
+        assert_eq!("land_nft_1/2", res.attributes.iter().find(|a| a.key == "token_id").unwrap().value);
+
+        // admins can burn any token
+        let burn = ExecuteMsg::BurnLandNft { token_id : "land_nft_1/2".to_string() };
+        execute(deps.as_mut(), mock_env(), admin.clone(), burn).unwrap();
+
+        let value = get_land_nft(deps.as_ref(), LandNft::key(1)).unwrap().land_nft;
+        assert_eq!(Some(LAND_NFT_STATUS_BURNED), value.status);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::NumOfMintedTokens {}).unwrap();
+        let value : cw721::NumTokensResponse = from_binary(&res).unwrap();
+        assert_eq!(0, value.count);
+
+        let msg = ExecuteMsg::RemoveLandNft { for_key : LandNft::key(1) };
+        execute(deps.as_mut(), mock_env(), admin, msg).unwrap();
+    }
+
+    #[test]
+    fn test_update_minted_land_nft(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+        let key = LandNft::key(1);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 100, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 1, 10, None );
+
+        let msg = ExecuteMsg::AddLandNftRoyalty { for_key : key.clone(), 
+            creator_wallet : "terra1qchccaxyrzk8a4yxu6y2vwzc48jak8qmqm9qtg".to_string(), index : 0, royalty : 250 };
+        execute(deps.as_mut(), mock_env(), admin.clone(), msg).unwrap();
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(10, "uusd"));
+        let msg = ExecuteMsg::MintLandNft { for_key : key.clone(), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        execute(deps.as_mut(), mock_env(), buyer.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::UpdateLandNft { for_key : key.clone(), name : Some("Lot 90".to_string()), description : None, 
+            total_size : 100, each_size : 100, size_unit : None, addr : "Tmn Sinar Bak Bak, Lot 90A".to_string(), 
+            total_lands : 1, price : 20, price_denom : None };
+        execute(deps.as_mut(), mock_env(), admin, msg).unwrap();
+
+        let value = get_land_nft(deps.as_ref(), key.clone()).unwrap().land_nft;
+        assert_eq!((Some("Lot 90".to_string()), Some("Tmn Sinar Bak Bak, Lot 90A".to_string()), 20), (value.name, value.addr, value.price));
+        assert_eq!((buyer.sender.clone(), Some(LAND_NFT_STATUS_MINTED)), (value.owner, value.status));
+
+        let value = get_land_nft_royalties(deps.as_ref(), key.clone()).unwrap();
+        assert_eq!(vec![250], value.royalties.iter().map(|r| r.royalty).collect::<Vec<u16>>());
+
+        let value = get_all_land_nfts_by_owner(deps.as_ref(), buyer.sender.to_string(), None, None).unwrap();
+        assert_eq!(1, value.land_nfts.len());
+    }
+
+    #[test]
+    fn test_indexed_queries(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        for n in 1..26 {
+
+            let denom = if n % 5 == 0 { "uluna" } else { "uusd" };
+
+            let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+            100, 100, None,  format!("Tmn Sinar Bak Bak, Lot {}", n), 1, 10, Some(denom.to_string()) );
+        }
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(10, "uusd"));
+
+        for n in [2, 3, 4, 12, 13, 14, 22] {
+
+            let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(n), plot : None, external_url_prefix : None, proof : None, allocation : None };
+            execute(deps.as_mut(), mock_env(), buyer.clone(), msg).unwrap();
+        }
+
+        // pages of unminted land are full, however the minted ones are spread
+        let mut keys : Vec<String> = vec![];
+        let mut start_after : Option<String> = None;
+
+        loop {
+
+            let value = get_all_land_nfts_by(deps.as_ref(), None, start_after, Some(5)).unwrap();
+
+            if value.land_nfts.is_empty() {
+                break;
             }
 
-            let m =  LandNftMediaType{
-                url : format!( "https://imgurl.ii/ImgX_{}", n * 1782),
-                is_default : is_def, 
-                media_type : MEDIA_TYPE_IMAGE,
-                date_updated : None, 
-            };
+            assert!(value.land_nfts.iter().all(|l| l.status.is_none()));
 
-            nft.add_media_type(m, mock_env().block.time);
+            start_after = value.land_nfts.last().unwrap().key.clone();
+            keys.extend(value.land_nfts.into_iter().map(|l| l.key.unwrap()));
         }
 
-        let url = nft.default_media_type_url(MEDIA_TYPE_IMAGE);
-        println!("default.img.url::{}", url.unwrap_or("Failed to unwrap url of default image!!!x".to_string()));
+        assert_eq!(18, keys.len());
+
+        let value = get_all_land_nfts_by(deps.as_ref(), Some(LAND_NFT_STATUS_MINTED), None, Some(20)).unwrap();
+        assert_eq!(7, value.land_nfts.len());
+
+        assert_eq!(18, land_nfts_count_by(deps.as_ref(), None).unwrap().count);
+        assert_eq!(7, land_nfts_count_by(deps.as_ref(), Some(LAND_NFT_STATUS_MINTED)).unwrap().count);
+
+        let value = get_by_index_in(deps.as_ref(), Some(LAND_NFT_STATUS_MINTED), 6).unwrap();
+        assert_eq!(Some(LandNft::key(4)), value.land_nft.unwrap().key);
+
+        let value = get_all_land_nfts_by_owner(deps.as_ref(), buyer.sender.to_string(), None, None).unwrap();
+        assert_eq!(7, value.land_nfts.len());
+
+        let value = get_all_land_nfts_by_denom(deps.as_ref(), "uluna".to_string(), None, None).unwrap();
+        assert_eq!(5, value.land_nfts.len());
+
+        let value = get_all_land_nfts_by_denom(deps.as_ref(), "uluna".to_string(), Some(LandNft::key(15)), None).unwrap();
+        assert_eq!(vec![Some(LandNft::key(20)), Some(LandNft::key(25)), Some(LandNft::key(5))], 
+        value.land_nfts.into_iter().map(|l| l.key).collect::<Vec<Option<String>>>());
+    }
+
+    #[test]
+    fn test_catalog_stats(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        for n in 1..16 {
+
+            let denom = if n % 5 == 0 { "uluna" } else { "uusd" };
+
+            let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+            100, 50, None,  format!("Tmn Sinar Bak Bak, Lot {}", n), 2, 10, Some(denom.to_string()) );
+        }
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(20, "uusd"));
+
+        let msg = ExecuteMsg::BatchMintLandNft { keys : vec![LandNft::key(1), LandNft::key(1)], external_url_prefix : None, proof : None, allocation : None };
+        execute(deps.as_mut(), mock_env(), buyer.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(2), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        execute(deps.as_mut(), mock_env(), mock_info(buyer.sender.as_str(), &coins(10, "uusd")), msg).unwrap();
+
+        let msg = ExecuteMsg::TransferNft { recipient : "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0".to_string(), 
+            token_id : "land_nft_1/1".to_string() };
+        execute(deps.as_mut(), mock_env(), buyer, msg).unwrap();
+
+        let msg = ExecuteMsg::RemoveLandNft { for_key : LandNft::key(15) };
+        execute(deps.as_mut(), mock_env(), admin, msg).unwrap();
+
+        assert_eq!(13, land_nfts_count_by(deps.as_ref(), None).unwrap().count);
+        assert_eq!(0, land_nfts_count_by(deps.as_ref(), Some(LAND_NFT_STATUS_MINTED)).unwrap().count);
+        assert_eq!(1, land_nfts_count_by(deps.as_ref(), Some(LAND_NFT_STATUS_TRANSFERRED)).unwrap().count);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::CatalogStats {}).unwrap();
+        let value : CatalogStatsResponse = from_binary(&res).unwrap();
+
+        assert_eq!(14, value.total);
+        assert_eq!(vec![StatusCount { status : 0, count : 13 }, 
+            StatusCount { status : LAND_NFT_STATUS_TRANSFERRED, count : 1 }], value.by_status);
+        assert_eq!(1400, value.total_area);
+        assert_eq!(vec![Coin::new(40, "uluna"), Coin::new(210, "uusd")], value.listed_values);
     }
 
+    #[test]
+    fn test_typed_errors(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let msg = ExecuteMsg::AddLandNftMediaType { for_key : "land_nft_99".to_string(), 
+            url : "https://imgurl.ii/Img".to_string(), media_type : MEDIA_TYPE_IMAGE, is_default : true };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), msg);
+        assert!(matches!(res, Err(ContractError::LandNftNotFound { key }) if key == "land_nft_99"));
+
+        let msg = ExecuteMsg::MintLandNft { for_key : "land_nft_99".to_string(), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), msg);
+        assert!(matches!(res, Err(ContractError::LandNftNotFound { .. })));
+
+        let msg = ExecuteMsg::AddAdmin { address : "x".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), msg);
+        assert!(matches!(res, Err(ContractError::InvalidAddress { address }) if address == "x"));
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 100, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 1, 10, None );
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(10, "uusd"));
+
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        execute(deps.as_mut(), mock_env(), buyer.clone(), msg.clone()).unwrap();
+
+        let res = execute(deps.as_mut(), mock_env(), buyer, msg);
+        assert!(matches!(res, Err(ContractError::AlreadyMinted { .. })));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetLandNft { key : "land_nft_99".to_string() });
+        assert!(matches!(res, Err(cosmwasm_std::StdError::NotFound { .. })));
+    }
 
     #[test]
-    fn test_pay(){
+    fn test_royalties(){
 
-        let res = crate::ins::pay_treasuries(120000, None, Some(true) );
-        println!("paid.res::{:?}", res);
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 2, 100, None );
+
+        let creator1 = "terra1qchccaxyrzk8a4yxu6y2vwzc48jak8qmqm9qtg";
+        let creator2 = "terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9";
+
+        let royalty = |creator : &str, index : u8, royalty : u16| ExecuteMsg::AddLandNftRoyalty { 
+            for_key : LandNft::key(1), creator_wallet : creator.to_string(), index, royalty };
+
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), royalty("x", 0, 100));
+        assert!(matches!(res, Err(ContractError::InvalidAddress { .. })));
+
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), royalty(creator1, 0, 0));
+        assert!(matches!(res, Err(ContractError::InvalidRoyalty { royalty : 0 })));
+
+        execute(deps.as_mut(), mock_env(), admin.clone(), royalty(creator1, 1, 250)).unwrap();
+
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), royalty(creator2, 1, 250));
+        assert!(matches!(res, Err(ContractError::DuplicateRoyaltyIndex { index : 1 })));
+
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), royalty(creator1, 2, 250));
+        assert!(matches!(res, Err(ContractError::DuplicateRoyaltyCreator { .. })));
+
+        // the default cap is 10%
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), royalty(creator2, 0, 800));
+        assert!(matches!(res, Err(ContractError::RoyaltyTotalExceeded { total : 1050, max : 1000 })));
+
+        execute(deps.as_mut(), mock_env(), admin.clone(), royalty(creator2, 0, 750)).unwrap();
+
+        let msg = QueryMsg::RoyaltyInfo { token_id : "land_nft_1/2".to_string(), sale_price : Uint128::from(1001u128) };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value : RoyaltyInfoResponse = from_binary(&res).unwrap();
+
+        assert_eq!(vec![
+            RoyaltyPayout { creator_wallet : Addr::unchecked(creator2), royalty : 750, amount : Uint128::from(75u128) },
+            RoyaltyPayout { creator_wallet : Addr::unchecked(creator1), royalty : 250, amount : Uint128::from(25u128) },
+        ], value.payouts);
+        assert_eq!(Uint128::from(100u128), value.total_amount);
+
+        let msg = ExecuteMsg::RemoveLandNftRoyalty { for_key : LandNft::key(1), creator_wallet : "x".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), msg);
+        assert!(matches!(res, Err(ContractError::InvalidAddress { .. })));
+
+        let msg = ExecuteMsg::RemoveLandNftRoyalty { for_key : LandNft::key(1), creator_wallet : "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), msg);
+        assert!(matches!(res, Err(ContractError::RoyaltyNotFound { .. })));
+
+        let msg = ExecuteMsg::RemoveLandNftRoyalty { for_key : LandNft::key(1), creator_wallet : creator2.to_string() };
+        execute(deps.as_mut(), mock_env(), admin.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::UpdateConfig { name : None, symbol : None, price_denom : None, 
+            external_url_prefix : None, minter : None, max_royalty_bps : Some(10001) };
+        let res = execute(deps.as_mut(), mock_env(), admin, msg);
+        assert!(matches!(res, Err(ContractError::InvalidRoyalty { .. })));
+    }
+
+    #[test]
+    fn test_cw2981(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 2, 100, None );
+
+        let creator1 = "terra1qchccaxyrzk8a4yxu6y2vwzc48jak8qmqm9qtg";
+        let creator2 = "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0";
+
+        let msg = ExecuteMsg::AddLandNftRoyalty { for_key : LandNft::key(1), creator_wallet : creator1.to_string(), index : 2, royalty : 250 };
+        execute(deps.as_mut(), mock_env(), admin.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::AddLandNftRoyalty { for_key : LandNft::key(1), creator_wallet : creator2.to_string(), index : 1, royalty : 450 };
+        execute(deps.as_mut(), mock_env(), admin, msg).unwrap();
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(100, "uusd"));
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        execute(deps.as_mut(), mock_env(), buyer, msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::NftInfo { token_id : "land_nft_1/1".to_string() }).unwrap();
+        let value : cw721::NftInfoResponse<Metadata> = from_binary(&res).unwrap();
+
+        assert_eq!((Some(7), Some(700)), (value.extension.royalty_percentage, value.extension.royalty_bps));
+        assert_eq!(Some(creator2.to_string()), value.extension.royalty_payment_address);
+
+        let msg = QueryMsg::Extension { msg : Cw2981QueryMsg::RoyaltyInfo { 
+            token_id : "land_nft_1/1".to_string(), sale_price : Uint128::from(1000u128) } };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value : RoyaltiesInfoResponse = from_binary(&res).unwrap();
+
+        assert_eq!(creator2.to_string(), value.address);
+        assert_eq!(Uint128::from(70u128), value.royalty_amount);
+
+        let msg = QueryMsg::Extension { msg : Cw2981QueryMsg::CheckRoyalties {} };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value : CheckRoyaltiesResponse = from_binary(&res).unwrap();
+        assert!(value.royalty_payments);
+    }
+
+    #[test]
+    fn test_cw2981_fractional_royalty(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 2, 100, None );
+
+        let creator = "terra1qchccaxyrzk8a4yxu6y2vwzc48jak8qmqm9qtg";
+
+        let msg = ExecuteMsg::AddLandNftRoyalty { for_key : LandNft::key(1), creator_wallet : creator.to_string(), index : 1, royalty : 250 };
+        execute(deps.as_mut(), mock_env(), admin.clone(), msg).unwrap();
+
+        let buyer = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(100, "uusd"));
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        execute(deps.as_mut(), mock_env(), buyer, msg).unwrap();
+
+        // the royalty changes after the plot was minted
+        let msg = ExecuteMsg::AddLandNftRoyalty { for_key : LandNft::key(1), 
+        creator_wallet : "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0".to_string(), index : 0, royalty : 400 };
+        execute(deps.as_mut(), mock_env(), admin, msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::NftInfo { token_id : "land_nft_1/1".to_string() }).unwrap();
+        let value : cw721::NftInfoResponse<Metadata> = from_binary(&res).unwrap();
+
+        // the cw2981 percentage is a whole number, the exact basis points answer the query
+        assert_eq!((Some(2), Some(250)), (value.extension.royalty_percentage, value.extension.royalty_bps));
+
+        let sale_price = Uint128::from(1000u128);
+        let msg = QueryMsg::Extension { msg : Cw2981QueryMsg::RoyaltyInfo { 
+            token_id : "land_nft_1/1".to_string(), sale_price } };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value : RoyaltiesInfoResponse = from_binary(&res).unwrap();
+
+        assert_eq!(creator.to_string(), value.address);
+        assert_eq!(Uint128::from(25u128), value.royalty_amount);
+        assert_eq!(royalty_amount(sale_price, 250), value.royalty_amount);
+    }
+
+    #[test]
+    fn test_marketplace(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 2, 100, None );
+
+        let msg = ExecuteMsg::AddLandNftRoyalty { for_key : LandNft::key(1), 
+            creator_wallet : "terra1qchccaxyrzk8a4yxu6y2vwzc48jak8qmqm9qtg".to_string(), index : 0, royalty : 500 };
+        execute(deps.as_mut(), mock_env(), admin.clone(), msg).unwrap();
+
+        let seller = mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(200, "uusd"));
+        let buyer = "buyer";
+
+        let msg = ExecuteMsg::BatchMintLandNft { keys : vec![LandNft::key(1), LandNft::key(1)], external_url_prefix : None, proof : None, allocation : None };
+        execute(deps.as_mut(), mock_env(), seller.clone(), msg).unwrap();
+
+        let list = |token_id : &str, price : u128, denom : &str| ExecuteMsg::ListForSale { token_id : token_id.to_string(), 
+            price : Uint128::from(price), denom : denom.to_string(), expires : None };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(buyer, &[]), list("land_nft_1/1", 1000, "uusd"));
+        assert!(matches!(res, Err(ContractError::Cw721(cw721_base::ContractError::Unauthorized {}))));
+
+        execute(deps.as_mut(), mock_env(), seller.clone(), list("land_nft_1/1", 1000, "uusd")).unwrap();
+        execute(deps.as_mut(), mock_env(), seller.clone(), list("land_nft_1/2", 40, "uluna")).unwrap();
+
+        // the listed tokens are held by the contract
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MintedTokensByOwner { owner : MOCK_CONTRACT_ADDR.to_string(), 
+            start_after : None, limit : None }).unwrap();
+        let value : cw721::TokensResponse = from_binary(&res).unwrap();
+        assert_eq!(2, value.tokens.len());
+
+        let value = get_listings(deps.as_ref(), Some("uusd".to_string()), None, None, None, None).unwrap();
+        assert_eq!(1, value.listings.len());
+
+        let value = get_listings(deps.as_ref(), None, Some(Uint128::from(50u128)), None, None, None).unwrap();
+        assert_eq!(vec!["land_nft_1/1".to_string()], value.listings.into_iter().map(|l| l.token_id).collect::<Vec<String>>());
+
+        let buy = ExecuteMsg::Buy { token_id : "land_nft_1/1".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(buyer, &coins(999, "uusd")), buy.clone());
+        assert!(matches!(res, Err(ContractError::InsufficientFunds { .. })));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(buyer, &coins(1010, "uusd")), buy.clone()).unwrap();
+
+        assert_eq!(vec![
+            CosmosMsg::Bank(BankMsg::Send { to_address : seller.sender.to_string(), amount : coins(950, "uusd") }),
+            CosmosMsg::Bank(BankMsg::Send { to_address : buyer.to_string(), amount : coins(10, "uusd") }),
+        ], res.messages.into_iter().map(|m| m.msg).collect::<Vec<CosmosMsg>>());
+
+        let value = get_pending_balance(deps.as_ref(), "terra1qchccaxyrzk8a4yxu6y2vwzc48jak8qmqm9qtg".to_string()).unwrap();
+        assert_eq!(coins(50, "uusd"), value.balances);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::AllNftInfo { token_id : "land_nft_1/1".to_string() }).unwrap();
+        let value : cw721::AllNftInfoResponse<Metadata> = from_binary(&res).unwrap();
+        assert_eq!(buyer.to_string(), value.access.owner);
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(buyer, &coins(1000, "uusd")), buy);
+        assert!(matches!(res, Err(ContractError::ListingNotFound { .. })));
+
+        let cancel = ExecuteMsg::CancelListing { token_id : "land_nft_1/2".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(buyer, &[]), cancel.clone());
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        execute(deps.as_mut(), mock_env(), seller.clone(), cancel).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::AllNftInfo { token_id : "land_nft_1/2".to_string() }).unwrap();
+        let value : cw721::AllNftInfoResponse<Metadata> = from_binary(&res).unwrap();
+        assert_eq!(seller.sender.to_string(), value.access.owner);
+
+        // expired listings can't be bought
+        let msg = ExecuteMsg::ListForSale { token_id : "land_nft_1/2".to_string(), price : Uint128::from(40u128), 
+            denom : "uluna".to_string(), expires : Some(cw721::Expiration::AtHeight(mock_env().block.height + 1)) };
+        execute(deps.as_mut(), mock_env(), seller, msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.height += 1;
+
+        let buy = ExecuteMsg::Buy { token_id : "land_nft_1/2".to_string() };
+        let res = execute(deps.as_mut(), env, mock_info(buyer, &coins(40, "uluna")), buy);
+        assert!(matches!(res, Err(ContractError::ListingExpired { .. })));
+    }
+
+    #[test]
+    fn test_auction(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 100, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 1, 100, None );
+
+        let now = mock_env().block.time;
+
+        let msg = ExecuteMsg::StartAuction { for_key : LandNft::key(1), reserve_price : Uint128::from(500u128), 
+            denom : "uusd".to_string(), start : now, end : now.plus_seconds(3600), min_increment : Uint128::from(50u128) };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bidder1", &[]), msg.clone());
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        execute(deps.as_mut(), mock_env(), admin.clone(), msg).unwrap();
+
+        // the fixed price sale waits for the auction
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9", &coins(100, "uusd")), msg);
+        assert!(matches!(res, Err(ContractError::AuctionActive { .. })));
+
+        let bid = ExecuteMsg::PlaceBid { for_key : LandNft::key(1) };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bidder1", &coins(499, "uusd")), bid.clone());
+        assert!(matches!(res, Err(ContractError::BidTooLow { .. })));
+
+        execute(deps.as_mut(), mock_env(), mock_info("bidder1", &coins(500, "uusd")), bid.clone()).unwrap();
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bidder2", &coins(549, "uusd")), bid.clone());
+        assert!(matches!(res, Err(ContractError::BidTooLow { .. })));
+
+        // a bid in the last minutes pushes the end back
+        let mut env = mock_env();
+        env.block.time = now.plus_seconds(3500);
+
+        execute(deps.as_mut(), env.clone(), mock_info("bidder2", &coins(600, "uusd")), bid).unwrap();
+
+        let value = get_pending_balance(deps.as_ref(), "bidder1".to_string()).unwrap();
+        assert_eq!(coins(500, "uusd"), value.balances);
+
+        let value = get_auction(deps.as_ref(), LandNft::key(1)).unwrap().auction.unwrap();
+        assert_eq!(2, value.bids.len());
+        assert_eq!(now.plus_seconds(3500 + AUCTION_TIME_EXTENSION), value.end);
+
+        let settle = ExecuteMsg::SettleAuction { for_key : LandNft::key(1) };
+
+        env.block.time = now.plus_seconds(3600);
+        let res = execute(deps.as_mut(), env.clone(), mock_info("bidder1", &[]), settle.clone());
+        assert!(matches!(res, Err(ContractError::AuctionNotEnded { .. })));
+
+        env.block.time = now.plus_seconds(3500 + AUCTION_TIME_EXTENSION);
+        let res = execute(deps.as_mut(), env.clone(), mock_info("bidder1", &[]), settle.clone()).unwrap();
+        assert_eq!("land_nft_1/1", res.attributes.iter().find(|a| a.key == "token_id").unwrap().value);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::AllNftInfo { token_id : "land_nft_1/1".to_string() }).unwrap();
+        let value : cw721::AllNftInfoResponse<Metadata> = from_binary(&res).unwrap();
+        assert_eq!("bidder2", value.access.owner);
+
+        let value = get_pending_balance(deps.as_ref(), "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0".to_string()).unwrap();
+        assert_eq!(coins(570, "uusd"), value.balances);
+
+        let res = execute(deps.as_mut(), env, mock_info("bidder1", &[]), settle);
+        assert!(matches!(res, Err(ContractError::AuctionNotEnded { .. })));
+    }
+
+    #[test]
+    fn test_auction_without_parcel(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 100, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 1, 100, None );
+
+        let now = mock_env().block.time;
+
+        let msg = ExecuteMsg::StartAuction { for_key : LandNft::key(1), reserve_price : Uint128::from(500u128), 
+            denom : "uusd".to_string(), start : now, end : now.plus_seconds(3600), min_increment : Uint128::from(50u128) };
+        execute(deps.as_mut(), mock_env(), admin.clone(), msg).unwrap();
+
+        let bid = ExecuteMsg::PlaceBid { for_key : LandNft::key(1) };
+        execute(deps.as_mut(), mock_env(), mock_info("bidder1", &coins(500, "uusd")), bid).unwrap();
+
+        // the parcel can't be taken away from under its bidders
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), ExecuteMsg::RemoveLandNft { for_key : LandNft::key(1) });
+        assert!(matches!(res, Err(ContractError::AuctionActive { .. })));
+
+        // and if it's gone anyway, settling refunds the winning bid instead of failing
+        remove_land_nft_entry(deps.as_mut().storage, LandNft::key(1).as_str()).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = now.plus_seconds(3600);
+
+        let res = execute(deps.as_mut(), env, mock_info("stranger", &[]), ExecuteMsg::SettleAuction { for_key : LandNft::key(1) }).unwrap();
+        assert!(res.attributes.iter().any(|a| a.key == "refunded" && a.value == "bidder1"));
+
+        let value = get_pending_balance(deps.as_ref(), "bidder1".to_string()).unwrap();
+        assert_eq!(coins(500, "uusd"), value.balances);
+
+        assert_eq!(None, get_auction(deps.as_ref(), LandNft::key(1)).unwrap().auction);
+    }
+
+    #[test]
+    fn test_dutch_auction(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        for n in 1..3 {
+            let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+            100, 100, None,  format!("Tmn Sinar Bak Bak, Lot {}", n), 2, 100, None );
+        }
+
+        let now = mock_env().block.time;
+
+        let dutch = |for_key : Option<String>, mode : u8| ExecuteMsg::SetDutchAuction { for_key, mode, 
+            start_price : Uint128::from(1000u128), floor : Uint128::from(400u128), start : now, 
+            decay_interval : 60, decay_step : Uint128::from(100u128) };
+
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), dutch(None, 0));
+        assert!(matches!(res, Err(ContractError::InvalidDutchAuction {})));
+
+        let msg = ExecuteMsg::SetDutchAuction { for_key : None, mode : DUTCH_AUCTION_LINEAR, 
+            start_price : Uint128::from(1000u128), floor : Uint128::from(400u128), start : now, 
+            decay_interval : 60, decay_step : Uint128::zero() };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), msg);
+        assert!(matches!(res, Err(ContractError::InvalidDutchAuction {})));
+
+        execute(deps.as_mut(), mock_env(), admin.clone(), dutch(None, DUTCH_AUCTION_LINEAR)).unwrap();
+        execute(deps.as_mut(), mock_env(), admin.clone(), dutch(Some(LandNft::key(2)), DUTCH_AUCTION_STEPPED)).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = now.plus_seconds(90);
+
+        let price = |deps : cosmwasm_std::Deps, env : cosmwasm_std::Env, key : String| get_current_price(deps, env, key).unwrap().price;
+
+        assert_eq!(Coin::new(850, "uusd"), price(deps.as_ref(), env.clone(), LandNft::key(1)));
+        assert_eq!(Coin::new(900, "uusd"), price(deps.as_ref(), env.clone(), LandNft::key(2)));
+
+        let mut later = mock_env();
+        later.block.time = now.plus_seconds(3600);
+        assert_eq!(Coin::new(400, "uusd"), price(deps.as_ref(), later, LandNft::key(1)));
+
+        // decay that overflows settles at the floor instead of panicking
+        for mode in [DUTCH_AUCTION_LINEAR, DUTCH_AUCTION_STEPPED] {
+            let auction = DutchAuction { mode, start_price : Uint128::MAX, floor : Uint128::from(400u128), 
+                start : now, decay_interval : 1, decay_step : Uint128::MAX };
+            assert_eq!(Uint128::from(400u128), auction.price_at(now.plus_seconds(10 * 365 * 24 * 3600)));
+        }
+
+        // minting charges the live price
+        let buyer = "terra1ek2jqqyyzm8ywwp8qwp6phmsaclq3uryg48vf9";
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : None, external_url_prefix : None, proof : None, allocation : None };
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info(buyer, &coins(849, "uusd")), msg.clone());
+        assert!(matches!(res, Err(ContractError::InsufficientFunds { .. })));
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info(buyer, &coins(1000, "uusd")), msg.clone()).unwrap();
+        assert_eq!(CosmosMsg::Bank(BankMsg::Send { to_address : buyer.to_string(), amount : coins(150, "uusd") }), res.messages[0].msg);
+
+        let msg = ExecuteMsg::ClearDutchAuction { for_key : None };
+        execute(deps.as_mut(), mock_env(), admin, msg).unwrap();
+
+        assert_eq!(Coin::new(100, "uusd"), price(deps.as_ref(), env.clone(), LandNft::key(1)));
+        assert_eq!(Coin::new(900, "uusd"), price(deps.as_ref(), env, LandNft::key(2)));
+    }
+
+    #[test]
+    fn test_sale_phases(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 10, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 10, 100, None );
+
+        let now = mock_env().block.time;
+        let alice = "alice";
+
+        let mint = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : None, external_url_prefix : None, proof : None, allocation : None };
+
+        // only minters mint until sale phases are set
+        let res = execute(deps.as_mut(), mock_env(), mock_info(alice, &coins(100, "uusd")), mint.clone());
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        let presale = SalePhase { name : "presale".to_string(), start : now, end : now.plus_seconds(1000), 
+            has_allowlist : true, mint_cap : Some(2), price : Some(Uint128::from(50u128)), merkle_root : None };
+        let public = SalePhase { name : "public".to_string(), start : now.plus_seconds(1000), end : now.plus_seconds(2000), 
+            has_allowlist : false, mint_cap : None, price : None, merkle_root : None };
+
+        let overlapping = SalePhase { start : now.plus_seconds(999), ..public.clone() };
+        let msg = ExecuteMsg::SetSalePhases { phases : vec![presale.clone(), overlapping] };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), msg);
+        assert!(matches!(res, Err(ContractError::InvalidSalePhases {})));
+
+        let msg = ExecuteMsg::SetSalePhases { phases : vec![public, presale] };
+        execute(deps.as_mut(), mock_env(), admin.clone(), msg).unwrap();
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(alice, &coins(50, "uusd")), mint.clone());
+        assert!(matches!(res, Err(ContractError::NotAllowlisted { .. })));
+
+        let msg = ExecuteMsg::AddToAllowlist { phase : "presale".to_string(), addresses : vec![alice.to_string(), "bob".to_string()] };
+        execute(deps.as_mut(), mock_env(), admin.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::RemoveFromAllowlist { phase : "presale".to_string(), addresses : vec!["bob".to_string()] };
+        execute(deps.as_mut(), mock_env(), admin, msg).unwrap();
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob", &coins(50, "uusd")), mint.clone());
+        assert!(matches!(res, Err(ContractError::NotAllowlisted { .. })));
+
+        execute(deps.as_mut(), mock_env(), mock_info(alice, &coins(50, "uusd")), mint.clone()).unwrap();
+
+        let value = get_mint_eligibility(deps.as_ref(), mock_env(), alice.to_string()).unwrap();
+        assert_eq!((true, Some("presale".to_string()), 1, Some(1)), (value.eligible, value.phase, value.minted, value.remaining));
+
+        let batch = ExecuteMsg::BatchMintLandNft { keys : vec![LandNft::key(1), LandNft::key(1)], external_url_prefix : None, proof : None, allocation : None };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(alice, &coins(100, "uusd")), batch);
+        assert!(matches!(res, Err(ContractError::MintCapReached { cap : 2, .. })));
+
+        // the public phase has neither allowlist nor cap, and charges the land nft's price
+        let mut env = mock_env();
+        env.block.time = now.plus_seconds(1500);
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info("bob", &coins(50, "uusd")), mint.clone());
+        assert!(matches!(res, Err(ContractError::InsufficientFunds { .. })));
+
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &coins(100, "uusd")), mint.clone()).unwrap();
+
+        env.block.time = now.plus_seconds(2000);
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info("bob", &coins(100, "uusd")), mint);
+        assert!(matches!(res, Err(ContractError::NoActiveSalePhase {})));
+
+        let value = get_mint_eligibility(deps.as_ref(), env, alice.to_string()).unwrap();
+        assert!(!value.eligible);
+    }
+
+    #[test]
+    fn test_merkle_allowlist(){
+
+        let entries = merkle::parse_csv(include_str!("../examples/allowlist.csv")).unwrap();
+        assert_eq!(5, entries.len());
+
+        let leaves = entries.iter().map(|(a, n)| merkle::leaf_hash(a, *n)).collect::<Vec<_>>();
+        let root = hex::encode(merkle::root(&leaves).unwrap());
+
+        let proof_of = |i : usize| merkle::proof(&leaves, i).iter().map(hex::encode).collect::<Vec<String>>();
+
+        // every entry verifies, including the odd one out, and a changed allocation doesn't
+        for (i, (address, allocation)) in entries.iter().enumerate() {
+
+            assert!(merkle::verify(&root, merkle::leaf_hash(address, *allocation), &proof_of(i)));
+            assert!(!merkle::verify(&root, merkle::leaf_hash(address, allocation + 1), &proof_of(i)));
+        }
+
+        assert!(merkle::parse_csv("alice").is_err());
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 10, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 10, 100, None );
+
+        let now = mock_env().block.time;
+
+        let presale = SalePhase { name : "presale".to_string(), start : now, end : now.plus_seconds(1000), 
+            has_allowlist : true, mint_cap : Some(3), price : Some(Uint128::from(50u128)), merkle_root : Some("abc".to_string()) };
+
+        let msg = ExecuteMsg::SetSalePhases { phases : vec![presale.clone()] };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), msg);
+        assert!(matches!(res, Err(ContractError::InvalidSalePhases {})));
+
+        // a root on a phase open to everyone is rejected
+        let open = SalePhase { merkle_root : Some(root.clone()), has_allowlist : false, ..presale.clone() };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), ExecuteMsg::SetSalePhases { phases : vec![open] });
+        assert!(matches!(res, Err(ContractError::InvalidSalePhases {})));
+
+        let msg = ExecuteMsg::SetSalePhases { phases : vec![SalePhase { merkle_root : Some(root), ..presale }] };
+        execute(deps.as_mut(), mock_env(), admin.clone(), msg).unwrap();
+
+        // without a proof even the on-chain allowlist doesn't get in
+        let msg = ExecuteMsg::AddToAllowlist { phase : "presale".to_string(), addresses : vec!["stranger".to_string()] };
+        execute(deps.as_mut(), mock_env(), admin, msg).unwrap();
+
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("stranger", &coins(50, "uusd")), msg);
+        assert!(matches!(res, Err(ContractError::NotAllowlisted { .. })));
+
+        assert!(!get_mint_eligibility(deps.as_ref(), mock_env(), "stranger".to_string()).unwrap().eligible);
+
+        let mint = |proof : Option<Vec<String>>, allocation : Option<u32>| ExecuteMsg::BatchMintLandNft { 
+            keys : vec![LandNft::key(1)], external_url_prefix : None, proof, allocation };
+
+        // alice is entry 0 with an allocation of 2
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(50, "uusd")), mint(None, None));
+        assert!(matches!(res, Err(ContractError::NotAllowlisted { .. })));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(50, "uusd")), mint(Some(proof_of(0)), Some(3)));
+        assert!(matches!(res, Err(ContractError::InvalidMerkleProof { .. })));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob", &coins(50, "uusd")), mint(Some(proof_of(0)), Some(2)));
+        assert!(matches!(res, Err(ContractError::InvalidMerkleProof { .. })));
+
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(50, "uusd")), mint(Some(proof_of(0)), Some(2))).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(50, "uusd")), mint(Some(proof_of(0)), Some(2))).unwrap();
+
+        // the allocation caps alice below the phase's cap
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(50, "uusd")), mint(Some(proof_of(0)), Some(2)));
+        assert!(matches!(res, Err(ContractError::MintCapReached { cap : 2, .. })));
+
+        // and the phase's cap applies to larger allocations
+        let msg = ExecuteMsg::BatchMintLandNft { keys : vec![LandNft::key(1); 4], external_url_prefix : None, 
+            proof : Some(proof_of(4)), allocation : Some(5) };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("erin", &coins(200, "uusd")), msg);
+        assert!(matches!(res, Err(ContractError::MintCapReached { cap : 3, .. })));
+
+        execute(deps.as_mut(), mock_env(), mock_info("erin", &coins(50, "uusd")), mint(Some(proof_of(4)), Some(5))).unwrap();
+    }
+
+    #[test]
+    fn test_reservations(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+        let treasury = "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0";
+
+        for n in 1..3 {
+            let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+            100, 100, None,  format!("Tmn Sinar Bak Bak, Lot {}", n), 1, 100, None );
+        }
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 10, None,  "Tmn Sinar Bak Bak, Lot 93".to_string(), 10, 100, None );
+
+        let now = mock_env().block.time;
+        let key = LandNft::key(1);
+
+        let reserve_of = |key : String| ExecuteMsg::ReserveLandNft { for_key : key, proof : None, allocation : None };
+        let mint_of = |key : String| ExecuteMsg::MintLandNft { for_key : key, plot : None, external_url_prefix : None, proof : None, allocation : None };
+
+        let reserve = reserve_of(key.clone());
+        let mint = mint_of(key.clone());
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(20, "uusd")), reserve.clone());
+        assert!(matches!(res, Err(ContractError::ReservationsDisabled {})));
+
+        let terms = ReservationTerms { deposit_bps : 0, duration : 600, forfeit : false };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), ExecuteMsg::SetReservationTerms { terms : Some(terms.clone()) });
+        assert!(matches!(res, Err(ContractError::InvalidReservationTerms {})));
+
+        let terms = ReservationTerms { deposit_bps : 2000, ..terms };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), ExecuteMsg::SetReservationTerms { terms : Some(terms.clone()) });
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+        execute(deps.as_mut(), mock_env(), admin.clone(), ExecuteMsg::SetReservationTerms { terms : Some(terms.clone()) }).unwrap();
+
+        // only those allowed to mint may reserve, minters only until sale phases are set
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(20, "uusd")), reserve.clone());
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        let public = SalePhase { name : "public".to_string(), start : now, end : now.plus_seconds(10000), 
+            has_allowlist : false, mint_cap : None, price : None, merkle_root : None };
+        execute(deps.as_mut(), mock_env(), admin.clone(), ExecuteMsg::SetSalePhases { phases : vec![public] }).unwrap();
+
+        // a reservation holds the whole land nft, so only single plots are reserved
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(20, "uusd")), reserve_of(LandNft::key(3)));
+        assert!(matches!(res, Err(ContractError::ReservationMultiplePlots { .. })));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(19, "uusd")), reserve.clone());
+        assert!(matches!(res, Err(ContractError::InsufficientFunds { .. })));
+
+        // the deposit is 20% of the price, the rest is refunded
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(25, "uusd")), reserve.clone()).unwrap();
+        assert_eq!(CosmosMsg::Bank(BankMsg::Send { to_address : "alice".to_string(), amount : coins(5, "uusd") }), res.messages[0].msg);
+
+        let value = get_reservation(deps.as_ref(), key.clone()).unwrap().reservation.unwrap();
+        assert_eq!((Addr::unchecked("alice"), Coin::new(20, "uusd"), now.plus_seconds(600)), (value.reserver, value.deposit, value.expires));
+        assert_eq!(Some(LAND_NFT_STATUS_RESERVED), get_land_nft(deps.as_ref(), key.clone()).unwrap().land_nft.status);
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob", &coins(20, "uusd")), reserve.clone());
+        assert!(matches!(res, Err(ContractError::LandNftReserved { .. })));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob", &coins(100, "uusd")), mint.clone());
+        assert!(matches!(res, Err(ContractError::LandNftReserved { .. })));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("stranger", &[]), ExecuteMsg::ReleaseReservation { for_key : key.clone() });
+        assert!(matches!(res, Err(ContractError::ReservationNotExpired { .. })));
+
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), ExecuteMsg::RemoveLandNft { for_key : key.clone() });
+        assert!(matches!(res, Err(ContractError::LandNftReserved { .. })));
+
+        // the deposit counts toward the price
+        let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(79, "uusd")), mint.clone());
+        assert!(matches!(res, Err(ContractError::InsufficientFunds { .. })));
+
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &coins(80, "uusd")), mint.clone()).unwrap();
+
+        assert_eq!(None, get_reservation(deps.as_ref(), key.clone()).unwrap().reservation);
+        assert_eq!(Some(LAND_NFT_STATUS_MINTED), get_land_nft(deps.as_ref(), key.clone()).unwrap().land_nft.status);
+
+        let value = get_pending_balance(deps.as_ref(), treasury.to_string()).unwrap();
+        assert_eq!(vec![Coin::new(95, "uusd")], value.balances);
+
+        // an expired reservation is refunded through the pending balances
+        let key = LandNft::key(2);
+        let reserve = reserve_of(key.clone());
+        let mint = mint_of(key.clone());
+
+        execute(deps.as_mut(), mock_env(), mock_info("bob", &coins(20, "uusd")), reserve.clone()).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = now.plus_seconds(600);
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info("bob", &coins(80, "uusd")), mint.clone());
+        assert!(matches!(res, Err(ContractError::LandNftReserved { .. })));
+
+        execute(deps.as_mut(), env.clone(), mock_info("stranger", &[]), ExecuteMsg::ReleaseReservation { for_key : key.clone() }).unwrap();
+
+        let value = get_pending_balance(deps.as_ref(), "bob".to_string()).unwrap();
+        assert_eq!(vec![Coin::new(20, "uusd")], value.balances);
+        assert_eq!(None, get_land_nft(deps.as_ref(), key.clone()).unwrap().land_nft.status);
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info("stranger", &[]), ExecuteMsg::ReleaseReservation { for_key : key.clone() });
+        assert!(matches!(res, Err(ContractError::ReservationNotFound { .. })));
+
+        // or forfeited to the treasuries
+        let terms = ReservationTerms { forfeit : true, ..terms };
+        execute(deps.as_mut(), env.clone(), admin, ExecuteMsg::SetReservationTerms { terms : Some(terms) }).unwrap();
+
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &coins(20, "uusd")), reserve).unwrap();
+
+        env.block.time = now.plus_seconds(1200);
+        execute(deps.as_mut(), env, mock_info("stranger", &[]), ExecuteMsg::ReleaseReservation { for_key : key }).unwrap();
+
+        let value = get_pending_balance(deps.as_ref(), "bob".to_string()).unwrap();
+        assert_eq!(vec![Coin::new(20, "uusd")], value.balances);
+
+        let value = get_pending_balance(deps.as_ref(), treasury.to_string()).unwrap();
+        assert_eq!(vec![Coin::new(114, "uusd")], value.balances);
+    }
+
+    #[test]
+    fn test_installments(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 2, 100, None );
+
+        let now = mock_env().block.time;
+        let key = LandNft::key(1);
+
+        let public = SalePhase { name : "public".to_string(), start : now, end : now.plus_seconds(10000), 
+            has_allowlist : false, mint_cap : None, price : None, merkle_root : None };
+        execute(deps.as_mut(), mock_env(), admin.clone(), ExecuteMsg::SetSalePhases { phases : vec![public] }).unwrap();
+
+        let at = |seconds : u64| { let mut env = mock_env(); env.block.time = now.plus_seconds(seconds); env };
+
+        let owner_of = |deps : &cosmwasm_std::OwnedDeps<_, _, _>, token_id : &str| {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::AllNftInfo { token_id : token_id.to_string() }).unwrap();
+            from_binary::<cw721::AllNftInfoResponse<Metadata>>(&res).unwrap().access.owner
+        };
+
+        let start = ExecuteMsg::StartPaymentPlan { for_key : key.clone(), proof : None, allocation : None };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob", &coins(40, "uusd")), start.clone());
+        assert!(matches!(res, Err(ContractError::PaymentPlanNotFound { .. })));
+
+        let plan = PaymentPlan { amounts : vec![], interval : 1000, grace_period : 500 };
+        let res = execute(deps.as_mut(), mock_env(), admin.clone(), ExecuteMsg::SetPaymentPlan { for_key : key.clone(), plan : Some(plan.clone()) });
+        assert!(matches!(res, Err(ContractError::InvalidPaymentPlan {})));
+
+        let plan = PaymentPlan { amounts : vec![Uint128::from(40u128), Uint128::from(30u128), Uint128::from(30u128)], ..plan };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), ExecuteMsg::SetPaymentPlan { for_key : key.clone(), plan : Some(plan.clone()) });
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+        execute(deps.as_mut(), mock_env(), admin.clone(), ExecuteMsg::SetPaymentPlan { for_key : key.clone(), plan : Some(plan.clone()) }).unwrap();
+
+        assert_eq!(Some(plan), get_payment_plan(deps.as_ref(), key.clone()).unwrap().plan);
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("bob", &coins(39, "uusd")), start.clone());
+        assert!(matches!(res, Err(ContractError::InsufficientFunds { .. })));
+
+        // the plot is minted into escrow against the first installment
+        execute(deps.as_mut(), mock_env(), mock_info("bob", &coins(40, "uusd")), start.clone()).unwrap();
+        assert_eq!(MOCK_CONTRACT_ADDR, owner_of(&deps, "land_nft_1/1"));
+
+        let value = get_installment_plan(deps.as_ref(), "land_nft_1/1".to_string()).unwrap();
+        assert_eq!((1, Some(now.plus_seconds(1000))), (value.plan.unwrap().paid, value.next_due));
+
+        let pay = ExecuteMsg::PayInstallment { token_id : "land_nft_1/1".to_string() };
+
+        let res = execute(deps.as_mut(), at(100), mock_info("alice", &coins(30, "uusd")), pay.clone());
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        execute(deps.as_mut(), at(100), mock_info("bob", &coins(30, "uusd")), pay.clone()).unwrap();
+
+        // paying on time pushes the next due date
+        let repossess = ExecuteMsg::RepossessLandNft { token_id : "land_nft_1/1".to_string() };
+        let res = execute(deps.as_mut(), at(2499), admin.clone(), repossess);
+        assert!(matches!(res, Err(ContractError::InstallmentNotOverdue { .. })));
+
+        // the last installment releases the token, overpaying is refunded
+        let res = execute(deps.as_mut(), at(2400), mock_info("bob", &coins(35, "uusd")), pay.clone()).unwrap();
+        assert_eq!(CosmosMsg::Bank(BankMsg::Send { to_address : "bob".to_string(), amount : coins(5, "uusd") }), res.messages[0].msg);
+        assert_eq!("bob", owner_of(&deps, "land_nft_1/1"));
+        assert_eq!(None, get_installment_plan(deps.as_ref(), "land_nft_1/1".to_string()).unwrap().plan);
+
+        let res = execute(deps.as_mut(), at(2400), mock_info("bob", &coins(30, "uusd")), pay);
+        assert!(matches!(res, Err(ContractError::InstallmentPlanNotFound { .. })));
+
+        // each installment is split on its own, the primary treasury takes the rounding
+        let value = get_pending_balance(deps.as_ref(), "terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0".to_string()).unwrap();
+        assert_eq!(vec![Coin::new(96, "uusd")], value.balances);
+
+        // a missed installment lets an admin take the plot back after the grace period
+        execute(deps.as_mut(), mock_env(), mock_info("carol", &coins(40, "uusd")), start).unwrap();
+        assert_eq!(Some(LAND_NFT_STATUS_MINTED), get_land_nft(deps.as_ref(), key.clone()).unwrap().land_nft.status);
+
+        let repossess = ExecuteMsg::RepossessLandNft { token_id : "land_nft_1/2".to_string() };
+
+        let res = execute(deps.as_mut(), at(1500), mock_info("bob", &[]), repossess.clone());
+        assert!(matches!(res, Err(ContractError::Unauthorized {})));
+
+        let res = execute(deps.as_mut(), at(1499), admin.clone(), repossess.clone());
+        assert!(matches!(res, Err(ContractError::InstallmentNotOverdue { .. })));
+
+        execute(deps.as_mut(), at(1500), admin, repossess).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::AllNftInfo { token_id : "land_nft_1/2".to_string() });
+        assert!(res.is_err());
+
+        let value = get_land_nft(deps.as_ref(), key.clone()).unwrap().land_nft;
+        assert_eq!((None, Some(1)), (value.status, value.minted_plots));
+
+        // the plot is back on sale
+        let mint = ExecuteMsg::MintLandNft { for_key : key, plot : None, external_url_prefix : None, proof : None, allocation : None };
+        execute(deps.as_mut(), at(1600), mock_info("dave", &coins(100, "uusd")), mint).unwrap();
+        assert_eq!("dave", owner_of(&deps, "land_nft_1/2"));
+    }
+
+    #[test]
+    fn test_repossess_transferred_land_nft(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let admin = mock_info("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3", &[]);
+
+        let _ = add_land_nft(deps.as_mut(),mock_env(), admin.clone(), None, None, 
+        100, 50, None,  "Tmn Sinar Bak Bak, Lot 90".to_string(), 2, 100, None );
+
+        let now = mock_env().block.time;
+        let key = LandNft::key(1);
+
+        let public = SalePhase { name : "public".to_string(), start : now, end : now.plus_seconds(10000), 
+            has_allowlist : false, mint_cap : None, price : None, merkle_root : None };
+        execute(deps.as_mut(), mock_env(), admin.clone(), ExecuteMsg::SetSalePhases { phases : vec![public] }).unwrap();
+
+        let plan = PaymentPlan { amounts : vec![Uint128::from(50u128), Uint128::from(50u128)], interval : 1000, grace_period : 500 };
+        execute(deps.as_mut(), mock_env(), admin.clone(), ExecuteMsg::SetPaymentPlan { for_key : key.clone(), plan : Some(plan) }).unwrap();
+
+        let start = ExecuteMsg::StartPaymentPlan { for_key : key.clone(), proof : None, allocation : None };
+        execute(deps.as_mut(), mock_env(), mock_info("bob", &coins(50, "uusd")), start).unwrap();
+
+        let mint = ExecuteMsg::MintLandNft { for_key : key.clone(), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        execute(deps.as_mut(), mock_env(), mock_info("dave", &coins(100, "uusd")), mint.clone()).unwrap();
+
+        let msg = ExecuteMsg::TransferNft { recipient : "erin".to_string(), token_id : "land_nft_1/2".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("dave", &[]), msg).unwrap();
+        assert_eq!(Some(LAND_NFT_STATUS_TRANSFERRED), get_land_nft(deps.as_ref(), key.clone()).unwrap().land_nft.status);
+
+        let mut env = mock_env();
+        env.block.time = now.plus_seconds(1500);
+
+        let repossess = ExecuteMsg::RepossessLandNft { token_id : "land_nft_1/1".to_string() };
+        execute(deps.as_mut(), env.clone(), admin, repossess).unwrap();
+
+        let value = get_land_nft(deps.as_ref(), key).unwrap().land_nft;
+        assert_eq!((None, Some(1)), (value.status, value.minted_plots));
+
+        // the repossessed plot is back on sale
+        let res = execute(deps.as_mut(), env, mock_info("frank", &coins(100, "uusd")), mint).unwrap();
+        let token_id = res.attributes.iter().find(|a| a.key == "token_id").map(|a| a.value.clone());
+        assert_eq!(Some("land_nft_1/1".to_string()), token_id);
+    }
+
+    /// cw-multi-test names contracts "Contract #n", which the mock api 
+    /// rejects as not normalized, so those are let through as they are
+    #[derive(Default)]
+    struct MultiTestApi(MockApi);
+
+    impl Api for MultiTestApi {
+
+        fn addr_validate(&self, human : &str) -> StdResult<Addr> {
+
+            if human.starts_with("Contract #") {
+                return Ok(Addr::unchecked(human));
+            }
+
+            self.0.addr_validate(human)
+        }
+
+        fn addr_canonicalize(&self, human : &str) -> StdResult<CanonicalAddr> {
+            self.0.addr_canonicalize(human)
+        }
+
+        fn addr_humanize(&self, canonical : &CanonicalAddr) -> StdResult<Addr> {
+            self.0.addr_humanize(canonical)
+        }
+
+        fn secp256k1_verify(&self, message_hash : &[u8], signature : &[u8], public_key : &[u8]) -> Result<bool, VerificationError> {
+            self.0.secp256k1_verify(message_hash, signature, public_key)
+        }
+
+        fn secp256k1_recover_pubkey(&self, message_hash : &[u8], signature : &[u8], recovery_param : u8) -> Result<Vec<u8>, RecoverPubkeyError> {
+            self.0.secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+        }
+
+        fn ed25519_verify(&self, message : &[u8], signature : &[u8], public_key : &[u8]) -> Result<bool, VerificationError> {
+            self.0.ed25519_verify(message, signature, public_key)
+        }
+
+        fn ed25519_batch_verify(&self, messages : &[&[u8]], signatures : &[&[u8]], public_keys : &[&[u8]]) -> Result<bool, VerificationError> {
+            self.0.ed25519_batch_verify(messages, signatures, public_keys)
+        }
+
+        fn debug(&self, message : &str) {
+            self.0.debug(message)
+        }
+    }
+
+    #[test]
+    fn test_cw20_payments(){
+
+        let mut app : App = AppBuilder::new().with_api(MultiTestApi::default()).build();
+
+        let admin = Addr::unchecked("admin");
+        let treasury = Addr::unchecked("treasury");
+        let buyer = Addr::unchecked("buyer");
+        let bidder = Addr::unchecked("bidder");
+
+        let land_code = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+        let cw20_code = app.store_code(Box::new(ContractWrapper::new(cw20_base::contract::execute, 
+            cw20_base::contract::instantiate, cw20_base::contract::query)));
+
+        let new_token = |app : &mut App, symbol : &str| {
+
+            let msg = cw20_base::msg::InstantiateMsg { name : symbol.to_string(), symbol : symbol.to_string(), decimals : 6, 
+                initial_balances : vec![Cw20Coin { address : buyer.to_string(), amount : Uint128::from(10000u128) },
+                    Cw20Coin { address : bidder.to_string(), amount : Uint128::from(10000u128) }], 
+                mint : None, marketing : None };
+
+            app.instantiate_contract(cw20_code, admin.clone(), &msg, &[], symbol, None).unwrap()
+        };
+
+        let token = new_token(&mut app, "NWLD");
+        let other_token = new_token(&mut app, "OTHER");
+        let denom = cw20_denom(&token);
+
+        let msg = InstantiateMsg { name : "Neworld Land NFT".to_string(), symbol : None, price_denom : None, 
+            external_url_prefix : None, minter : None, admins : vec![admin.to_string()], 
+            treasuries : vec![TreasuryMsg { wallet_address : treasury.to_string(), basis_points : 10000, is_primary : true }],
+            max_royalty_bps : None };
+        let land = app.instantiate_contract(land_code, admin.clone(), &msg, &[], "land", None).unwrap();
+
+        let now = app.block_info().time;
+
+        // the second land nft is a single plot so it can be reserved
+        for (each_size, total_lands) in [(50, 2), (100, 1)] {
+
+            let msg = ExecuteMsg::AddLandNft { name : None, description : None, total_size : 100, each_size, size_unit : None, 
+                addr : "Tmn Sinar Bak Bak, Lot 90".to_string(), total_lands, price : 100, price_denom : None };
+            app.execute_contract(admin.clone(), land.clone(), &msg, &[]).unwrap();
+        }
+
+        let public = SalePhase { name : "public".to_string(), start : now, end : now.plus_seconds(10000), 
+            has_allowlist : false, mint_cap : None, price : None, merkle_root : None };
+        app.execute_contract(admin.clone(), land.clone(), &ExecuteMsg::SetSalePhases { phases : vec![public] }, &[]).unwrap();
+
+        for key in [LandNft::key(1), LandNft::key(2)] {
+
+            let msg = ExecuteMsg::SetCw20Price { for_key : key, contract : token.to_string(), price : Some(Uint128::from(500u128)) };
+            app.execute_contract(admin.clone(), land.clone(), &msg, &[]).unwrap();
+        }
+
+        let value : Cw20PricesResponse = app.wrap().query_wasm_smart(land.clone(), &QueryMsg::Cw20Prices { for_key : LandNft::key(1) }).unwrap();
+        assert_eq!(vec![Cw20CoinVerified { address : token.clone(), amount : Uint128::from(500u128) }], value.prices);
+
+        let send = |app : &mut App, from : &Addr, token : &Addr, amount : u128, msg : Cw20HookMsg| {
+
+            let msg = Cw20ExecuteMsg::Send { contract : land.to_string(), amount : Uint128::from(amount), msg : to_binary(&msg).unwrap() };
+            app.execute_contract(from.clone(), token.clone(), &msg, &[])
+        };
+
+        let balance = |app : &App, address : &Addr| {
+
+            let res : cw20::BalanceResponse = app.wrap().query_wasm_smart(token.clone(), 
+                &cw20::Cw20QueryMsg::Balance { address : address.to_string() }).unwrap();
+            res.balance.u128()
+        };
+
+        let owner_of = |app : &App, token_id : &str| {
+
+            let res : cw721::AllNftInfoResponse<Metadata> = app.wrap().query_wasm_smart(land.clone(), 
+                &QueryMsg::AllNftInfo { token_id : token_id.to_string() }).unwrap();
+            res.access.owner
+        };
+
+        let mint = |key : String| Cw20HookMsg::MintLandNft { for_key : key, plot : None, external_url_prefix : None, proof : None, allocation : None };
+
+        let err = send(&mut app, &buyer, &other_token, 500, mint(LandNft::key(1))).unwrap_err();
+        assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::Cw20NotAccepted { .. })));
+
+        let err = send(&mut app, &buyer, &token, 499, mint(LandNft::key(1))).unwrap_err();
+        assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::InsufficientFunds { .. })));
+
+        // the overpaid tokens are sent back
+        send(&mut app, &buyer, &token, 520, mint(LandNft::key(1))).unwrap();
+        assert_eq!(buyer.to_string(), owner_of(&app, "land_nft_1/1"));
+        assert_eq!((9500, 500), (balance(&app, &buyer), balance(&app, &land)));
+
+        // cw20 denoms can't be passed off as native funds
+        app.init_bank_balance(&buyer, coins(500, denom.as_str())).unwrap();
+        let msg = ExecuteMsg::MintLandNft { for_key : LandNft::key(1), plot : None, external_url_prefix : None, proof : None, allocation : None };
+        let err = app.execute_contract(buyer.clone(), land.clone(), &msg, &coins(500, denom.as_str())).unwrap_err();
+        assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::Cw20NotAccepted { .. })));
+
+        // the treasury's share is paid out as a cw20 transfer
+        let msg = ExecuteMsg::Withdraw { denom : denom.clone(), amount : Uint128::from(500u128) };
+        app.execute_contract(treasury.clone(), land.clone(), &msg, &[]).unwrap();
+        assert_eq!((500, 0), (balance(&app, &treasury), balance(&app, &land)));
+
+        // resales
+        let msg = ExecuteMsg::ListForSale { token_id : "land_nft_1/1".to_string(), price : Uint128::from(300u128), denom : denom.clone(), expires : None };
+        app.execute_contract(buyer.clone(), land.clone(), &msg, &[]).unwrap();
+
+        send(&mut app, &bidder, &token, 300, Cw20HookMsg::Buy { token_id : "land_nft_1/1".to_string() }).unwrap();
+        assert_eq!(bidder.to_string(), owner_of(&app, "land_nft_1/1"));
+        assert_eq!((9800, 9700), (balance(&app, &buyer), balance(&app, &bidder)));
+
+        // auctions, the outbid bidder withdraws the refund
+        let msg = ExecuteMsg::StartAuction { for_key : LandNft::key(1), reserve_price : Uint128::from(100u128), denom : denom.clone(), 
+            start : now, end : now.plus_seconds(1000), min_increment : Uint128::from(10u128) };
+        app.execute_contract(admin.clone(), land.clone(), &msg, &[]).unwrap();
+
+        send(&mut app, &bidder, &token, 150, Cw20HookMsg::PlaceBid { for_key : LandNft::key(1) }).unwrap();
+        send(&mut app, &buyer, &token, 200, Cw20HookMsg::PlaceBid { for_key : LandNft::key(1) }).unwrap();
+
+        let msg = ExecuteMsg::Withdraw { denom : denom.clone(), amount : Uint128::from(150u128) };
+        app.execute_contract(bidder.clone(), land.clone(), &msg, &[]).unwrap();
+        assert_eq!(9700, balance(&app, &bidder));
+
+        app.update_block(|b| b.time = b.time.plus_seconds(1000));
+
+        app.execute_contract(admin.clone(), land.clone(), &ExecuteMsg::SettleAuction { for_key : LandNft::key(1) }, &[]).unwrap();
+        assert_eq!(buyer.to_string(), owner_of(&app, "land_nft_1/2"));
+
+        // reservations, the cw20 deposit counts toward the cw20 price
+        let terms = ReservationTerms { deposit_bps : 2000, duration : 600, forfeit : false };
+        app.execute_contract(admin.clone(), land.clone(), &ExecuteMsg::SetReservationTerms { terms : Some(terms) }, &[]).unwrap();
+
+        send(&mut app, &bidder, &token, 100, Cw20HookMsg::ReserveLandNft { for_key : LandNft::key(2), proof : None, allocation : None }).unwrap();
+
+        let err = send(&mut app, &buyer, &token, 500, mint(LandNft::key(2))).unwrap_err();
+        assert!(matches!(err.downcast_ref::<ContractError>(), Some(ContractError::LandNftReserved { .. })));
+
+        send(&mut app, &bidder, &token, 400, mint(LandNft::key(2))).unwrap();
+        assert_eq!(bidder.to_string(), owner_of(&app, "land_nft_2/1"));
+
+        let value : PendingBalanceResponse = app.wrap().query_wasm_smart(land, 
+            &QueryMsg::PendingBalance { address : treasury.to_string() }).unwrap();
+        assert_eq!(vec![Coin { denom, amount : Uint128::from(700u128) }], value.balances);
+    }
+
+    #[test]
+    fn test_funcs(){
+
+        let mut nft = LandNft::default(mock_env().block.time);
+        
+        for n in 1..4 {
+
+            let mut is_def = false ;
+
+            if n == 2 {
+                is_def = true ;
+            }
+
+            let m =  LandNftMediaType{
+                url : format!( "https://imgurl.ii/ImgX_{}", n * 1782),
+                is_default : is_def, 
+                media_type : MEDIA_TYPE_IMAGE,
+                date_updated : None, 
+            };
+
+            nft.add_media_type(m, mock_env().block.time);
+        }
+
+        let url = nft.default_media_type_url(MEDIA_TYPE_IMAGE);
+        println!("default.img.url::{}", url.unwrap_or("Failed to unwrap url of default image!!!x".to_string()));
+    }
+
+
+    #[test]
+    fn test_pay(){
+
+        let mut deps = mock_dependencies(&[]);
+        setup_contract(deps.as_mut());
+
+        let config = get_config(deps.as_ref()).unwrap().config;
+
+        let res = crate::ins::treasury_shares(&config.treasuries, Uint128::new(120000));
+        println!("paid.res::{:?}", res);
+
+        assert_eq!(vec![
+            (Addr::unchecked("terra1c4kq5cft2df40q3tr9y0uum6cpksjf0f4y7zz0"), Uint128::new(114000)),
+            (Addr::unchecked("terra19c4jcex5zkdky00qqjpu5u5usvjk7wklxsajp3"), Uint128::new(6000)),
+        ], res.unwrap());
     }
 
     macro_rules! show_size {